@@ -0,0 +1,418 @@
+use crate::PWQuality;
+
+/// Password policy standards that a configuration can be audited against.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Standard {
+    /// NIST SP 800-63B.
+    Nist80063b,
+    /// PCI-DSS 4.0.
+    PciDss4,
+    /// CIS benchmark for Linux distributions.
+    Cis,
+}
+
+impl Standard {
+    /// All the standards.
+    pub const ALL: [Standard; 3] = [Standard::Nist80063b, Standard::PciDss4, Standard::Cis];
+
+    /// Get the name of the standard.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Standard::Nist80063b => "nist-800-63b",
+            Standard::PciDss4 => "pci-dss-4.0",
+            Standard::Cis => "cis",
+        }
+    }
+
+    /// Get the title of the standard.
+    pub fn title(&self) -> &'static str {
+        match self {
+            Standard::Nist80063b => "NIST SP 800-63B",
+            Standard::PciDss4 => "PCI-DSS 4.0",
+            Standard::Cis => "CIS benchmark",
+        }
+    }
+}
+
+impl std::fmt::Display for Standard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.title())
+    }
+}
+
+impl std::str::FromStr for Standard {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Standard::ALL
+            .into_iter()
+            .find(|p| p.name().eq_ignore_ascii_case(s))
+            .ok_or(format!("Unknown standard: {s}"))
+    }
+}
+
+/// Outcome of a single requirement, for an audit or a checklist.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Status {
+    /// The requirement is satisfied.
+    Met,
+    /// The requirement isn't satisfied.
+    Unmet,
+    /// The requirement can't be enforced by libpwquality, or can't be evaluated.
+    NotApplicable,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Status::Met => "MET",
+            Status::Unmet => "UNMET",
+            Status::NotApplicable => "N/A",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+/// Audit result of a single requirement.
+#[derive(Clone, Debug)]
+pub struct Finding {
+    /// Section of the standard the requirement comes from.
+    pub section: &'static str,
+    /// Short description of the requirement.
+    pub requirement: &'static str,
+    /// Whether the requirement is met.
+    pub status: Status,
+    /// Human-readable explanation of the status.
+    pub reason: String,
+}
+
+/// Audit report of a configuration against a standard.
+#[derive(Clone, Debug)]
+pub struct AuditReport {
+    /// The audited standard.
+    pub standard: Standard,
+    /// Findings for each requirement of the standard.
+    pub findings: Vec<Finding>,
+}
+
+impl AuditReport {
+    /// Whether no requirement of the standard is unmet.
+    pub fn is_compliant(&self) -> bool {
+        self.findings.iter().all(|f| f.status != Status::Unmet)
+    }
+}
+
+impl std::fmt::Display for AuditReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let verdict = if self.is_compliant() {
+            "compliant"
+        } else {
+            "not compliant"
+        };
+        writeln!(f, "{}: {verdict}", self.standard)?;
+
+        for finding in &self.findings {
+            writeln!(
+                f,
+                "  [{}] {} {}: {}",
+                finding.status, finding.section, finding.requirement, finding.reason
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn finding(section: &'static str, requirement: &'static str, met: bool, reason: String) -> Finding {
+    Finding {
+        section,
+        requirement,
+        status: if met { Status::Met } else { Status::Unmet },
+        reason,
+    }
+}
+
+fn not_applicable(section: &'static str, requirement: &'static str, reason: &str) -> Finding {
+    Finding {
+        section,
+        requirement,
+        status: Status::NotApplicable,
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(not(any(
+    feature = "v1_4_1",
+    feature = "vendored",
+    feature = "vendored-cracklib"
+)))]
+const UNSUPPORTED: &str = "the setting is not supported by the linked libpwquality version";
+
+fn min_length(pwq: &PWQuality, section: &'static str, required: i32) -> Finding {
+    let length = pwq.effective_min_length();
+    let reason = if length == pwq.get_min_length() {
+        format!("passwords must have at least {length} characters, {required} are required")
+    } else {
+        format!(
+            "minlen is {} but credits allow passwords of {length} characters, {required} are required",
+            pwq.get_min_length()
+        )
+    };
+
+    finding(section, "minimum length", length >= required, reason)
+}
+
+fn enforcing(pwq: &PWQuality, section: &'static str) -> Finding {
+    #[cfg(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib"))]
+    {
+        let met = pwq.get_enforcing();
+        let reason = if met {
+            "rejected passwords can't be used"
+        } else {
+            "enforcing is disabled, rejected passwords are only warned about"
+        };
+
+        finding(section, "enforcement", met, reason.to_string())
+    }
+
+    #[cfg(not(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib")))]
+    {
+        let _ = pwq;
+        finding(
+            section,
+            "enforcement",
+            true,
+            "rejected passwords can't be used".to_string(),
+        )
+    }
+}
+
+fn dict_check(pwq: &PWQuality, section: &'static str) -> Finding {
+    #[cfg(any(feature = "v1_3", feature = "vendored", feature = "vendored-cracklib"))]
+    {
+        let met = pwq.get_dict_check();
+        let reason = if met {
+            "passwords are checked against the cracklib dictionary"
+        } else {
+            "dictcheck is disabled"
+        };
+
+        finding(section, "dictionary check", met, reason.to_string())
+    }
+
+    #[cfg(not(any(feature = "v1_3", feature = "vendored", feature = "vendored-cracklib")))]
+    {
+        let _ = pwq;
+        finding(
+            section,
+            "dictionary check",
+            true,
+            "passwords are always checked against the cracklib dictionary".to_string(),
+        )
+    }
+}
+
+fn nist(pwq: &PWQuality) -> Vec<Finding> {
+    let negative_credits = [
+        pwq.get_digit_credit(),
+        pwq.get_uppercase_credit(),
+        pwq.get_lowercase_credit(),
+        pwq.get_other_credit(),
+    ]
+    .into_iter()
+    .filter(|&c| c < 0)
+    .count();
+    let no_composition = pwq.get_min_class() <= 1 && negative_credits == 0;
+    let composition_reason = if no_composition {
+        "no character classes are required".to_string()
+    } else {
+        format!(
+            "minclass is {} and {negative_credits} credits are negative, composition rules shall not be imposed",
+            pwq.get_min_class()
+        )
+    };
+
+    #[cfg(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib"))]
+    let user_check = {
+        let met = pwq.get_user_check();
+        let reason = if met {
+            "passwords containing the user name are rejected"
+        } else {
+            "usercheck is disabled"
+        };
+
+        finding("5.1.1.2", "context-specific words", met, reason.to_string())
+    };
+
+    #[cfg(not(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib")))]
+    let user_check = finding(
+        "5.1.1.2",
+        "context-specific words",
+        true,
+        "passwords containing the user name are always rejected".to_string(),
+    );
+
+    vec![
+        min_length(pwq, "5.1.1.2", 8),
+        finding(
+            "5.1.1.2",
+            "no composition rules",
+            no_composition,
+            composition_reason,
+        ),
+        dict_check(pwq, "5.1.1.2"),
+        user_check,
+        not_applicable(
+            "5.1.1.2",
+            "no periodic changes",
+            "password expiration is configured outside of libpwquality",
+        ),
+        enforcing(pwq, "5.1.1.2"),
+    ]
+}
+
+fn pci_dss(pwq: &PWQuality) -> Vec<Finding> {
+    let digits = pwq.get_digit_credit() < 0;
+    let letters = pwq.get_lowercase_credit() < 0 || pwq.get_uppercase_credit() < 0;
+    let reason = match (digits, letters) {
+        (true, true) => "digits and letters are required",
+        (true, false) => "digits are required, letters are not",
+        (false, true) => "letters are required, digits are not",
+        (false, false) => "neither digits nor letters are required",
+    };
+
+    vec![
+        min_length(pwq, "8.3.6", 12),
+        finding(
+            "8.3.6",
+            "numeric and alphabetic characters",
+            digits && letters,
+            reason.to_string(),
+        ),
+        not_applicable(
+            "8.3.7",
+            "password history",
+            "reuse of the last four passwords is checked by pam_pwhistory",
+        ),
+        enforcing(pwq, "8.3.6"),
+    ]
+}
+
+fn cis(pwq: &PWQuality) -> Vec<Finding> {
+    let credits = [
+        pwq.get_digit_credit(),
+        pwq.get_uppercase_credit(),
+        pwq.get_lowercase_credit(),
+        pwq.get_other_credit(),
+    ];
+    let min_class = pwq.get_min_class();
+    let complexity = min_class >= 4 || credits.iter().all(|&c| c < 0);
+    let complexity_reason = if complexity {
+        "all four character classes are required".to_string()
+    } else {
+        format!("minclass is {min_class}, all four character classes are required")
+    };
+
+    let min_diff = pwq.get_min_diff();
+    let max_repeat = pwq.get_max_repeat();
+
+    let mut findings = vec![
+        min_length(pwq, "5.3.1", 14),
+        finding(
+            "5.3.1",
+            "password complexity",
+            complexity,
+            complexity_reason,
+        ),
+        finding(
+            "5.3.1",
+            "changed characters",
+            min_diff >= 2,
+            format!("difok is {min_diff}, at least 2 is required"),
+        ),
+        finding(
+            "5.3.1",
+            "consecutive characters",
+            (1..=3).contains(&max_repeat),
+            format!("maxrepeat is {max_repeat}, 1 to 3 is required"),
+        ),
+    ];
+
+    #[cfg(any(feature = "v1_2", feature = "vendored", feature = "vendored-cracklib"))]
+    {
+        let max_sequence = pwq.get_max_sequence();
+        findings.push(finding(
+            "5.3.1",
+            "sequential characters",
+            (1..=3).contains(&max_sequence),
+            format!("maxsequence is {max_sequence}, 1 to 3 is required"),
+        ));
+    }
+
+    #[cfg(not(any(feature = "v1_2", feature = "vendored", feature = "vendored-cracklib")))]
+    findings.push(not_applicable(
+        "5.3.1",
+        "sequential characters",
+        UNSUPPORTED,
+    ));
+
+    findings.push(dict_check(pwq, "5.3.1"));
+    findings.push(enforcing(pwq, "5.3.1"));
+
+    #[cfg(any(
+        feature = "v1_4_1",
+        feature = "vendored",
+        feature = "vendored-cracklib"
+    ))]
+    {
+        let retry_times = pwq.get_retry_times();
+        findings.push(finding(
+            "5.3.1",
+            "retries",
+            retry_times <= 3,
+            format!("retry is {retry_times}, at most 3 are allowed"),
+        ));
+
+        let enforce_for_root = pwq.get_enforce_for_root();
+        let reason = if enforce_for_root {
+            "the policy is enforced for root"
+        } else {
+            "enforce_for_root is disabled"
+        };
+        findings.push(finding(
+            "5.3.1",
+            "enforced for root",
+            enforce_for_root,
+            reason.to_string(),
+        ));
+    }
+
+    #[cfg(not(any(
+        feature = "v1_4_1",
+        feature = "vendored",
+        feature = "vendored-cracklib"
+    )))]
+    {
+        findings.push(not_applicable("5.3.1", "retries", UNSUPPORTED));
+        findings.push(not_applicable("5.3.1", "enforced for root", UNSUPPORTED));
+    }
+
+    findings
+}
+
+impl PWQuality {
+    /// Audit the current settings against the given standard.
+    pub fn audit(&self, standard: Standard) -> AuditReport {
+        let findings = match standard {
+            Standard::Nist80063b => nist(self),
+            Standard::PciDss4 => pci_dss(self),
+            Standard::Cis => cis(self),
+        };
+
+        AuditReport { standard, findings }
+    }
+}
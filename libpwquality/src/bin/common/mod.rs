@@ -0,0 +1,8 @@
+//! Helpers shared by the command-line tools.
+
+#![allow(dead_code)]
+
+#[path = "../../json.rs"]
+mod json;
+
+pub(crate) use json::json_string;
@@ -0,0 +1,109 @@
+//! Generate random passwords, like upstream `pwmake`.
+//!
+//! Exit codes:
+//!
+//! * 0: the passwords were generated.
+//! * 1: password generation failed.
+//! * 2: memory allocation failed.
+//! * 3: invalid command-line arguments.
+//! * 4: the configuration file can't be read.
+
+mod common;
+
+use common::json_string;
+use libpwquality::PWQuality;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: pwmake [--config <file>] [--count <n>] [--json] <entropy-bits>\n\
+                     \n\
+                     Generate random passwords with the given entropy bits.";
+
+struct Args {
+    config: Option<String>,
+    count: usize,
+    json: bool,
+    bits: i32,
+}
+
+fn parse_args() -> Option<Args> {
+    let mut config = None;
+    let mut count = 1;
+    let mut json = false;
+    let mut bits = None;
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-c" | "--config" => config = Some(iter.next()?),
+            "-n" | "--count" => count = iter.next()?.parse().ok()?,
+            "-j" | "--json" => json = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            s if s.starts_with('-') => return None,
+            _ if bits.is_some() => return None,
+            _ => bits = Some(arg.parse().ok()?),
+        }
+    }
+
+    Some(Args {
+        config,
+        count,
+        json,
+        bits: bits?,
+    })
+}
+
+fn error(args: &Args, msg: &str, code: u8) -> ExitCode {
+    if args.json {
+        println!("{{\"error\":{}}}", json_string(msg));
+    } else {
+        eprintln!("Error: {msg}");
+    }
+
+    ExitCode::from(code)
+}
+
+fn main() -> ExitCode {
+    let Some(args) = parse_args() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(3);
+    };
+
+    let pwq = match PWQuality::new() {
+        Ok(pwq) => pwq,
+        Err(e) => return error(&args, &e.to_string(), 2),
+    };
+
+    let ret = match &args.config {
+        Some(path) => pwq.read_config(path),
+        None => pwq.read_default_config(),
+    };
+    if let Err(e) = ret {
+        return error(&args, &e.to_string(), 4);
+    }
+
+    let passwords = match (0..args.count)
+        .map(|_| pwq.generate(args.bits))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(passwords) => passwords,
+        Err(e) => return error(&args, &e.to_string(), 1),
+    };
+
+    if args.json {
+        let list = passwords
+            .iter()
+            .map(|p| json_string(p))
+            .collect::<Vec<_>>()
+            .join(",");
+        println!("{{\"passwords\":[{list}]}}");
+    } else {
+        for password in passwords {
+            println!("{password}");
+        }
+    }
+
+    ExitCode::SUCCESS
+}
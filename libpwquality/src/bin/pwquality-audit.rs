@@ -0,0 +1,62 @@
+//! Audit a pwquality configuration against password policy standards.
+
+use libpwquality::{PWQuality, Standard};
+use std::process::ExitCode;
+
+const DEFAULT_CONFIG: &str = "/etc/security/pwquality.conf";
+
+fn usage() -> String {
+    let names = Standard::ALL.map(|s| s.name()).join(", ");
+
+    format!(
+        "Usage: pwquality-audit [--config <file>] [--standard <name>]...\n\
+         \n\
+         Audit the pwquality configuration (default: {DEFAULT_CONFIG}).\n\
+         Available standards: {names}"
+    )
+}
+
+fn run() -> Result<bool, Box<dyn std::error::Error>> {
+    let mut config = DEFAULT_CONFIG.to_string();
+    let mut standards = Vec::new();
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-c" | "--config" => config = args.next().ok_or_else(usage)?,
+            "-s" | "--standard" => standards.push(args.next().ok_or_else(usage)?.parse()?),
+            "-h" | "--help" => {
+                println!("{}", usage());
+                return Ok(true);
+            }
+            _ => return Err(usage().into()),
+        }
+    }
+
+    if standards.is_empty() {
+        standards.extend(Standard::ALL);
+    }
+
+    let pwq = PWQuality::new()?;
+    pwq.read_config(&config)?;
+
+    let mut compliant = true;
+    for standard in standards {
+        let report = pwq.audit(standard);
+        compliant &= report.is_compliant();
+        print!("{report}");
+    }
+
+    Ok(compliant)
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("pwquality-audit: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
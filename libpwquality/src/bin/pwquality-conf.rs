@@ -0,0 +1,84 @@
+//! Edit pwquality configuration files in place.
+
+use libpwquality::{ConfigFile, DROP_IN_DIR};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const DEFAULT_CONFIG: &str = "/etc/security/pwquality.conf";
+
+const USAGE: &str = "Usage: pwquality-conf [--file <file> | --drop-in <name>] <command>\n\
+                     \n\
+                     Commands:\n\
+                     \x20 get <key>            Print the value of a setting\n\
+                     \x20 set <key> [<value>]  Set a setting, omit the value for flags\n\
+                     \x20 unset <key>          Remove a setting\n\
+                     \x20 list                 List the active settings";
+
+fn run() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let mut path = PathBuf::from(DEFAULT_CONFIG);
+    let mut drop_in = false;
+    let mut args = std::env::args().skip(1).peekable();
+
+    while let Some(arg) = args.next_if(|a| a.starts_with('-')) {
+        match arg.as_str() {
+            "-f" | "--file" => path = args.next().ok_or(USAGE)?.into(),
+            "-d" | "--drop-in" => {
+                path = ConfigFile::drop_in_path(&args.next().ok_or(USAGE)?)?;
+                drop_in = true;
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(ExitCode::SUCCESS);
+            }
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    let command = args.next().ok_or(USAGE)?;
+    let key = args.next();
+    let value = args.next();
+    if args.next().is_some() {
+        return Err(USAGE.into());
+    }
+
+    let mut conf = ConfigFile::read(&path)?;
+
+    match (command.as_str(), key, value) {
+        ("get", Some(key), None) => match conf.get(&key) {
+            Some(value) => println!("{}", value.unwrap_or_default()),
+            None => return Ok(ExitCode::from(1)),
+        },
+        ("set", Some(key), value) => {
+            conf.set(&key, value.as_deref())?;
+            if drop_in {
+                std::fs::create_dir_all(DROP_IN_DIR)?;
+            }
+            conf.write(&path)?;
+        }
+        ("unset", Some(key), None) => {
+            if conf.unset(&key) {
+                conf.write(&path)?;
+            } else {
+                return Ok(ExitCode::from(1));
+            }
+        }
+        ("list", None, None) => {
+            for (key, value) in conf.entries() {
+                match value {
+                    Some(value) => println!("{key} = {value}"),
+                    None => println!("{key}"),
+                }
+            }
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    run().unwrap_or_else(|e| {
+        eprintln!("pwquality-conf: {e}");
+        ExitCode::from(2)
+    })
+}
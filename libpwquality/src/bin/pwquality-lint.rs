@@ -0,0 +1,56 @@
+//! Lint pwquality configuration files.
+
+use libpwquality::lint_config;
+use std::process::ExitCode;
+
+const DEFAULT_CONFIG: &str = "/etc/security/pwquality.conf";
+
+fn main() -> ExitCode {
+    let mut files: Vec<String> = std::env::args().skip(1).collect();
+
+    if files.iter().any(|f| f == "-h" || f == "--help") {
+        println!(
+            "Usage: pwquality-lint [file]...\n\
+             \n\
+             Lint pwquality configuration files (default: {DEFAULT_CONFIG})."
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    if files.is_empty() {
+        files.push(DEFAULT_CONFIG.to_string());
+    }
+
+    let mut errors = false;
+    for file in files {
+        let report = match std::fs::read_to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|text| lint_config(&text).map_err(|e| e.to_string()))
+        {
+            Ok(report) => report,
+            Err(e) => {
+                eprintln!("pwquality-lint: {file}: {e}");
+                return ExitCode::from(2);
+            }
+        };
+
+        for diagnostic in &report.diagnostics {
+            match diagnostic.line {
+                Some(_) => println!("{file}:{diagnostic}"),
+                None => println!("{file}: {diagnostic}"),
+            }
+        }
+        println!(
+            "{file}: effective minimum password length: {}",
+            report.effective_min_length
+        );
+
+        errors |= report.has_errors();
+    }
+
+    if errors {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
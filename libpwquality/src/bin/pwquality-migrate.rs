@@ -0,0 +1,97 @@
+//! Migrate pam_cracklib and pam_pwquality module arguments to pwquality.conf.
+
+use libpwquality::migrate_pam;
+use std::process::ExitCode;
+
+const PAM_DIR: &str = "/etc/pam.d";
+
+fn usage() {
+    println!(
+        "Usage: pwquality-migrate [--output|-o FILE] [file]...\n\
+         \n\
+         Translate the pam_cracklib and pam_pwquality arguments of PAM stack files\n\
+         (default: all files in {PAM_DIR}) to an equivalent pwquality.conf, printed\n\
+         to stdout or written to FILE. The changes of the PAM stack files and the\n\
+         arguments that couldn't be translated are printed to stderr."
+    );
+}
+
+fn read_dir() -> std::io::Result<Vec<String>> {
+    let mut files = std::fs::read_dir(PAM_DIR)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .map(|entry| entry.path().display().to_string())
+        .collect::<Vec<_>>();
+    files.sort();
+
+    Ok(files)
+}
+
+fn main() -> ExitCode {
+    let mut output = None;
+    let mut files = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                usage();
+                return ExitCode::SUCCESS;
+            }
+            "-o" | "--output" => match args.next() {
+                Some(file) => output = Some(file),
+                None => {
+                    usage();
+                    return ExitCode::from(2);
+                }
+            },
+            _ => files.push(arg),
+        }
+    }
+
+    if files.is_empty() {
+        files = match read_dir() {
+            Ok(files) => files,
+            Err(e) => {
+                eprintln!("pwquality-migrate: {PAM_DIR}: {e}");
+                return ExitCode::from(2);
+            }
+        };
+    }
+
+    let mut stack = Vec::new();
+    for file in files {
+        match std::fs::read(&file) {
+            Ok(text) => stack.push((file, String::from_utf8_lossy(&text).to_string())),
+            Err(e) => {
+                eprintln!("pwquality-migrate: {file}: {e}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let migration = match migrate_pam(stack) {
+        Ok(migration) => migration,
+        Err(e) => {
+            eprintln!("pwquality-migrate: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match output {
+        Some(file) => {
+            if let Err(e) = migration.config.write(&file) {
+                eprintln!("pwquality-migrate: {file}: {e}");
+                return ExitCode::from(2);
+            }
+        }
+        None => print!("{}", migration.config),
+    }
+    eprint!("{}", migration.diff());
+
+    if migration.untranslated.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
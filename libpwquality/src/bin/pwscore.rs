@@ -0,0 +1,128 @@
+//! Compute the quality score of a password read from stdin, like upstream `pwscore`.
+//!
+//! Exit codes:
+//!
+//! * 0: the password passed the check, its score is printed.
+//! * 1: the password failed the check.
+//! * 2: memory allocation failed.
+//! * 3: invalid command-line arguments.
+//! * 4: the configuration file can't be read.
+//! * 5: the password can't be read from stdin, such as when stdin is empty.
+
+mod common;
+
+use common::json_string;
+use libpwquality::PWQuality;
+use std::ffi::OsString;
+use std::io::BufRead;
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: pwscore [--config <file>] [--json] [user]\n\
+                     \n\
+                     Read a password from stdin and print its quality score.";
+
+struct Args {
+    config: Option<PathBuf>,
+    json: bool,
+    user: Option<OsString>,
+}
+
+fn parse_args() -> Option<Args> {
+    let mut args = Args {
+        config: None,
+        json: false,
+        user: None,
+    };
+    // the user name may not be valid UTF-8
+    let mut iter = std::env::args_os().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.to_str().unwrap_or_default() {
+            "-c" | "--config" => args.config = Some(iter.next()?.into()),
+            "-j" | "--json" => args.json = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            _ if arg.as_bytes().starts_with(b"-") => return None,
+            _ if args.user.is_some() => return None,
+            _ => args.user = Some(arg),
+        }
+    }
+
+    Some(args)
+}
+
+fn error(args: &Args, msg: &str, code: u8) -> ExitCode {
+    if args.json {
+        println!("{{\"error\":{}}}", json_string(msg));
+    } else {
+        eprintln!("Error: {msg}");
+    }
+
+    ExitCode::from(code)
+}
+
+fn read_password() -> Option<Vec<u8>> {
+    let mut buf = Vec::new();
+    std::io::stdin().lock().read_until(b'\n', &mut buf).ok()?;
+    if buf.is_empty() {
+        return None;
+    }
+
+    // only the first line is the password, an empty line is checked as an empty password
+    if buf.last() == Some(&b'\n') {
+        buf.pop();
+    }
+
+    (!buf.contains(&0)).then_some(buf)
+}
+
+fn main() -> ExitCode {
+    let Some(args) = parse_args() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(3);
+    };
+
+    let pwq = match PWQuality::new() {
+        Ok(pwq) => pwq,
+        Err(e) => return error(&args, &e.to_string(), 2),
+    };
+
+    let ret = match &args.config {
+        Some(path) => pwq.read_config(path),
+        None => pwq.read_default_config(),
+    };
+    if let Err(e) = ret {
+        return error(&args, &e.to_string(), 4);
+    }
+
+    let Some(password) = read_password() else {
+        return error(&args, "Could not obtain the password to check", 5);
+    };
+
+    match pwq.check_bytes(
+        &password,
+        None,
+        args.user.as_deref().map(OsStrExt::as_bytes),
+    ) {
+        Ok(score) if args.json => {
+            println!("{{\"score\":{score}}}");
+            ExitCode::SUCCESS
+        }
+        Ok(score) => {
+            println!("{score}");
+            ExitCode::SUCCESS
+        }
+        Err(e) if args.json => {
+            println!("{{\"error\":{}}}", json_string(&e.to_string()));
+            ExitCode::from(1)
+        }
+        Err(e) => {
+            eprintln!("Password quality check failed:\n {e}");
+            ExitCode::from(1)
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::checklist::{distance, Input};
+use crate::context::contains_word;
+use crate::{
+    ErrorKind, PWQError, PWQuality, Requirement, Result, Similarity, SimilarityReason, Status,
+    UserContext,
+};
+
+/// Information about a password change given to [`PWQuality::check_with`], for this call
+/// only.
+///
+/// Nothing of the context changes the settings, so that a shared instance can check the
+/// passwords of several users or tenants.
+#[derive(Clone, Debug, Default)]
+pub struct CheckContext<'a> {
+    /// The old password, compared with the password by libpwquality.
+    pub old_password: Option<&'a str>,
+    /// The user name, checked by libpwquality. The login of the user context is used
+    /// if not set.
+    pub user: Option<&'a str>,
+    /// Account information that must not appear in the password, see [`UserContext`].
+    pub user_context: Option<&'a UserContext>,
+    /// Forbidden words checked like the configured `badwords`.
+    pub words: &'a [&'a str],
+    /// The passwords used before the old password, most recent first.
+    pub history: &'a [&'a str],
+    /// Metrics deciding whether the password is too similar to the old password, see
+    /// [`PWQuality::similarity`].
+    pub metrics: &'a [Similarity],
+    /// The hashes of the previous passwords.
+    #[cfg(feature = "history")]
+    pub store: Option<&'a crate::PasswordHistory>,
+}
+
+impl PWQuality {
+    /// Check the password according to the settings and the context.
+    ///
+    /// The password is first checked like [`check`](Self::check), with the old password
+    /// and the user name of the context, then against the rest of the context:
+    ///
+    /// * The user context is checked like the user name and the GECOS field. Unlike the
+    ///   GECOS field, which libpwquality looks up in the passwd database, the context
+    ///   doesn't need the user to exist on the system, which suits web accounts.
+    /// * The words are checked like the configured `badwords`. The matched word, whether
+    ///   configured or given, is reported by [`PWQError::param`] and
+    ///   [`PWQError::aux_str`].
+    /// * Each password of the history is compared with the password like libpwquality
+    ///   compares the old password: it must not be the same, differ with case changes
+    ///   only, be too similar according to `difok` unless it is at least twice as long,
+    ///   or be a rotation of it.
+    /// * The password must not be too similar to the old password according to the
+    ///   metrics.
+    /// * The password must not be one of the hashed store.
+    ///
+    /// The errors of the history and the metrics explain the similarity with
+    /// [`PWQError::similarity_reason`].
+    pub fn check_with(&self, password: &str, context: &CheckContext) -> Result<i32> {
+        let user = context
+            .user
+            .or_else(|| context.user_context.and_then(|c| c.login.as_deref()));
+        let lower = password.as_bytes().to_ascii_lowercase();
+
+        let score = match self.check(password, context.old_password, user) {
+            Err(e) if e.kind() == ErrorKind::BadWords => {
+                let word = self
+                    .get_bad_words()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .find(|word| contains_word(&lower, word));
+
+                return Err(word.map_or(e, |word| PWQError::with_str(ErrorKind::BadWords, &word)));
+            }
+            result => result?,
+        };
+
+        if let Some(kind) = context
+            .user_context
+            .and_then(|user_context| self.check_context(password, user_context))
+        {
+            return Err(PWQError::new(kind.code()));
+        }
+
+        if let Some(word) = context
+            .words
+            .iter()
+            .find(|word| contains_word(&lower, word))
+        {
+            return Err(PWQError::with_str(ErrorKind::BadWords, word));
+        }
+
+        if let Some(reason) = context
+            .old_password
+            .and_then(|old_password| Self::similarity(password, old_password, context.metrics))
+        {
+            return Err(PWQError::similar(reason));
+        }
+
+        if let Some(reason) = context
+            .history
+            .iter()
+            .find_map(|old_password| self.compare_old(password, old_password))
+        {
+            return Err(PWQError::similar(reason));
+        }
+
+        #[cfg(feature = "history")]
+        if context.store.is_some_and(|store| store.contains(password)) {
+            return Err(PWQError::new(ErrorKind::SamePassword.code()));
+        }
+
+        Ok(score)
+    }
+
+    /// Explain why the password is rejected when compared with an old password by the
+    /// rules of libpwquality, in the order it applies them.
+    fn compare_old(&self, password: &str, old_password: &str) -> Option<SimilarityReason> {
+        if password == old_password {
+            return Some(SimilarityReason::Same);
+        }
+
+        let input = Input::new(self, password, Some(old_password), None);
+        let old = input.old.as_deref().unwrap_or_default();
+        let unmet = |requirement: Requirement| requirement.status(&input) == Status::Unmet;
+
+        if input.lower == old {
+            Some(SimilarityReason::CaseSwapped)
+        } else if unmet(Requirement::MinDiff(self.get_min_diff())) {
+            Some(SimilarityReason::Edits(distance(old, &input.lower)))
+        } else if unmet(Requirement::NotOldVariant) {
+            Some(SimilarityReason::Rotated)
+        } else {
+            None
+        }
+    }
+}
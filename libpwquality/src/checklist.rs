@@ -0,0 +1,261 @@
+use crate::{CharClass, PWQuality, Requirement, Status};
+use std::ops::Range;
+
+/// A requirement and whether a password satisfies it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ChecklistItem {
+    /// The requirement.
+    pub requirement: Requirement,
+    /// Whether the password satisfies the requirement.
+    pub status: Status,
+}
+
+/// The password with its class counts, and the old password and the user name lowercased
+/// as libpwquality compares them.
+pub(crate) struct Input<'a> {
+    pub(crate) password: &'a [u8],
+    pub(crate) lower: Vec<u8>,
+    pub(crate) old: Option<Vec<u8>>,
+    pub(crate) user: Option<Vec<u8>>,
+    pub(crate) counts: [i32; 4],
+    pub(crate) credits: [i32; 4],
+}
+
+impl<'a> Input<'a> {
+    pub(crate) fn new(
+        pwq: &PWQuality,
+        password: &'a str,
+        old_password: Option<&str>,
+        user: Option<&str>,
+    ) -> Self {
+        let password = password.as_bytes();
+        let mut counts = [0; 4];
+        for &b in password {
+            counts[CharClass::of_byte(b) as usize] += 1;
+        }
+
+        Self {
+            password,
+            lower: password.to_ascii_lowercase(),
+            old: old_password.map(|s| s.as_bytes().to_ascii_lowercase()),
+            user: user.map(|s| s.as_bytes().to_ascii_lowercase()),
+            counts,
+            credits: [
+                pwq.get_digit_credit(),
+                pwq.get_uppercase_credit(),
+                pwq.get_lowercase_credit(),
+                pwq.get_other_credit(),
+            ],
+        }
+    }
+
+    /// Number of characters earning a credit, each of which lowers the required length.
+    pub(crate) fn earned_credits(&self) -> i32 {
+        self.counts
+            .iter()
+            .zip(self.credits)
+            .filter(|(_, credit)| *credit > 0)
+            .map(|(&count, credit)| count.min(credit))
+            .sum()
+    }
+}
+
+fn status(met: bool) -> Status {
+    if met {
+        Status::Met
+    } else {
+        Status::Unmet
+    }
+}
+
+pub(crate) fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    needle.is_empty() || haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+/// Range of the first occurrence of the needle.
+pub(crate) fn find(haystack: &[u8], needle: &[u8]) -> Option<Range<usize>> {
+    haystack
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|i| i..i + needle.len())
+}
+
+/// Range in the lowercased password of the first substring of the user name of this
+/// length, forwards or reversed.
+pub(crate) fn user_substr(lower: &[u8], user: &[u8], len: usize) -> Option<Range<usize>> {
+    user.windows(len).find_map(|part| {
+        let reversed = part.iter().rev().copied().collect::<Vec<_>>();
+
+        find(lower, part).or_else(|| find(lower, &reversed))
+    })
+}
+
+/// Range of the longest run of equal consecutive items.
+pub(crate) fn longest_run<T: PartialEq>(items: &[T]) -> Range<usize> {
+    let mut longest = 0..0;
+    let mut start = 0;
+
+    for i in 0..items.len() {
+        if i > 0 && items[i] != items[i - 1] {
+            start = i;
+        }
+        if i + 1 - start > longest.len() {
+            longest = start..i + 1;
+        }
+    }
+
+    longest
+}
+
+/// Range of the longest monotonic sequence such as `abc` or `321`.
+pub(crate) fn longest_sequence(password: &[u8]) -> Range<usize> {
+    let mut longest = 0..password.len().min(1);
+    // start of the current ascending and descending sequences
+    let (mut up, mut down) = (0, 0);
+
+    for i in 1..password.len() {
+        match i16::from(password[i]) - i16::from(password[i - 1]) {
+            1 => down = i,
+            -1 => up = i,
+            _ => (up, down) = (i, i),
+        }
+
+        let start = up.min(down);
+        if i + 1 - start > longest.len() {
+            longest = start..i + 1;
+        }
+    }
+
+    longest
+}
+
+/// Widen a byte range of the password to the boundaries of its characters.
+pub(crate) fn widen(password: &str, range: Range<usize>) -> Range<usize> {
+    let start = (0..=range.start)
+        .rev()
+        .find(|&i| password.is_char_boundary(i))
+        .unwrap_or(0);
+    let end = (range.end..=password.len())
+        .find(|&i| password.is_char_boundary(i))
+        .unwrap_or(password.len());
+
+    start..end
+}
+
+/// Levenshtein distance between two byte strings.
+pub(crate) fn distance(a: &[u8], b: &[u8]) -> usize {
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, &ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+impl Requirement {
+    /// Evaluate the requirement without calling into libpwquality.
+    pub(crate) fn status(&self, input: &Input) -> Status {
+        let class = |class: CharClass| input.counts[class as usize];
+
+        match self {
+            Requirement::MinLength { length, .. } => {
+                status(input.password.len() as i32 >= length - input.earned_credits())
+            }
+            Requirement::MinChars { class: c, count } => status(class(*c) >= *count),
+            Requirement::Credit { class: c, .. } => {
+                if class(*c) > 0 {
+                    Status::Met
+                } else {
+                    Status::NotApplicable
+                }
+            }
+            Requirement::MinClass(min) => {
+                status(input.counts.iter().filter(|&&c| c > 0).count() as i32 >= *min)
+            }
+            Requirement::MaxRepeat(max) => status(longest_run(input.password).len() as i32 <= *max),
+            Requirement::MaxClassRepeat(max) => {
+                let classes = input
+                    .password
+                    .iter()
+                    .map(|&b| CharClass::of_byte(b))
+                    .collect::<Vec<_>>();
+
+                status(longest_run(&classes).len() as i32 <= *max)
+            }
+            Requirement::MaxSequence(max) => {
+                status(longest_sequence(input.password).len() as i32 <= *max)
+            }
+            Requirement::MinDiff(min) => match &input.old {
+                Some(old) => status(
+                    distance(old, &input.lower) >= *min as usize
+                        || input.lower.len() >= old.len() * 2,
+                ),
+                None => Status::NotApplicable,
+            },
+            Requirement::NotPalindrome => status(!input.lower.iter().eq(input.lower.iter().rev())),
+            Requirement::NotOldVariant => match &input.old {
+                Some(old) => {
+                    let wrapped = [old.as_slice(), old.as_slice()].concat();
+                    status(*old != input.lower && !contains(&wrapped, &input.lower))
+                }
+                None => Status::NotApplicable,
+            },
+            // the dictionary and the passwd file are too expensive to look up
+            Requirement::NoDictionaryWord | Requirement::NoGecos => Status::NotApplicable,
+            Requirement::NoUserName => match &input.user {
+                Some(user) => {
+                    let reversed = user.iter().rev().copied().collect::<Vec<_>>();
+                    status(!contains(&input.lower, user) && !contains(&input.lower, &reversed))
+                }
+                None => Status::NotApplicable,
+            },
+            // like libpwquality, substrings of 3 characters or fewer are not checked
+            Requirement::UserSubstr(len) => match &input.user {
+                Some(user) if *len > 3 && user.len() >= *len as usize => {
+                    status(user_substr(&input.lower, user, *len as usize).is_none())
+                }
+                Some(_) => Status::Met,
+                None => Status::NotApplicable,
+            },
+            Requirement::NoBadWords(words) => status(!words.iter().any(|word| {
+                word.len() > 3 && contains(&input.lower, word.to_ascii_lowercase().as_bytes())
+            })),
+        }
+    }
+}
+
+impl PWQuality {
+    /// Evaluate each requirement of the settings against a partially typed password.
+    ///
+    /// Unlike [`check`](Self::check), every requirement is evaluated and the result
+    /// doesn't depend on the order of the checks. The evaluation is done in Rust without
+    /// looking up the cracklib dictionary or the passwd file, so it's cheap enough to run
+    /// on every keystroke, and the requirements that need them are reported as
+    /// [`Status::NotApplicable`]. A full [`check`](Self::check) is still needed before
+    /// accepting the password.
+    pub fn checklist(
+        &self,
+        password: &str,
+        old_password: Option<&str>,
+        user: Option<&str>,
+    ) -> Vec<ChecklistItem> {
+        let input = Input::new(self, password, old_password, user);
+
+        self.requirements()
+            .into_iter()
+            .map(|requirement| ChecklistItem {
+                status: requirement.status(&input),
+                requirement,
+            })
+            .collect()
+    }
+}
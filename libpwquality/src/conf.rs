@@ -0,0 +1,227 @@
+use crate::{ErrorKind, PWQError, Result, SettingInfo};
+use std::path::Path;
+
+/// Directory of the drop-in configuration files.
+pub const DROP_IN_DIR: &str = "/etc/security/pwquality.conf.d";
+
+#[derive(Clone, Debug)]
+enum Line {
+    /// An active `key = value` or `key` line, `raw` is kept until the line is modified.
+    Setting {
+        key: String,
+        value: Option<String>,
+        comment: Option<String>,
+        raw: Option<String>,
+    },
+    /// A comment, a blank line or anything else that is kept verbatim.
+    Other(String),
+}
+
+/// A `pwquality.conf` document that keeps comments, blank lines and ordering when edited.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigFile {
+    lines: Vec<Line>,
+}
+
+fn parse_line(line: &str) -> Line {
+    let (content, comment) = match line.find('#') {
+        Some(i) => (&line[..i], Some(line[i..].to_string())),
+        None => (line, None),
+    };
+    let content = content.trim();
+
+    if content.is_empty() {
+        return Line::Other(line.to_string());
+    }
+
+    let (key, value) = match content.split_once('=') {
+        Some((key, value)) => (key.trim(), Some(value.trim().to_string())),
+        None => (content, None),
+    };
+
+    Line::Setting {
+        key: key.to_string(),
+        value,
+        comment,
+        raw: Some(line.to_string()),
+    }
+}
+
+fn is_setting(line: &Line, key: &str) -> bool {
+    matches!(line, Line::Setting { key: k, .. } if k == key)
+}
+
+/// Whether the line is a commented-out setting of the key, e.g. `# minlen = 8`.
+fn is_commented_out(line: &str, key: &str) -> bool {
+    line.trim_start()
+        .strip_prefix('#')
+        .map(str::trim_start)
+        .and_then(|s| s.strip_prefix(key))
+        .is_some_and(|s| s.is_empty() || s.trim_start().starts_with('='))
+}
+
+/// Check a setting with the registry before it is written.
+fn validate(key: &str, value: Option<&str>) -> Result<()> {
+    #[cfg(feature = "regex")]
+    if key == crate::pattern::DENY_PATTERN_KEY {
+        return value
+            .unwrap_or_default()
+            .parse::<crate::DenyPattern>()
+            .map(|_| ())
+            .map_err(|e| PWQError::with_str(ErrorKind::CfgfileMalformed, &e));
+    }
+
+    let info = SettingInfo::find(key)
+        .filter(|info| info.supported)
+        .ok_or_else(|| PWQError::with_str(ErrorKind::UnknownSetting, key))?;
+
+    info.validate(value)
+        .map_err(|e| PWQError::with_str(ErrorKind::Integer, &format!("{key}: {e}")))
+}
+
+impl ConfigFile {
+    /// Parse the content of a configuration file.
+    pub fn parse(text: &str) -> Self {
+        Self {
+            lines: text.lines().map(parse_line).collect(),
+        }
+    }
+
+    /// Read the configuration file, an empty document is returned if it doesn't exist.
+    pub fn read<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Ok(Self::parse(&text)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write the configuration file, replacing it atomically.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+
+        std::fs::write(&tmp, self.to_string())?;
+        if let Ok(metadata) = std::fs::metadata(path) {
+            std::fs::set_permissions(&tmp, metadata.permissions())?;
+        }
+
+        std::fs::rename(tmp, path)
+    }
+
+    /// Get the path of the drop-in file with the given name.
+    ///
+    /// Names containing `/` or `..`, or starting with `.`, are rejected so that the file
+    /// stays in [`DROP_IN_DIR`] and isn't hidden.
+    pub fn drop_in_path(name: &str) -> std::result::Result<std::path::PathBuf, String> {
+        if name.is_empty() || name.contains('/') || name.contains("..") || name.starts_with('.') {
+            return Err(format!("Bad drop-in file name: {name:?}"));
+        }
+
+        Ok(Path::new(DROP_IN_DIR).join(format!("{name}.conf")))
+    }
+
+    /// Get the value of a setting, `Some(None)` is returned for a setting without value.
+    ///
+    /// If the setting appears several times, the last value is returned as it's the one
+    /// used by libpwquality.
+    pub fn get(&self, key: &str) -> Option<Option<&str>> {
+        self.entries()
+            .filter(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+            .last()
+    }
+
+    /// Iterate over the active settings in file order.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.lines.iter().filter_map(|line| match line {
+            Line::Setting { key, value, .. } => Some((key.as_str(), value.as_deref())),
+            Line::Other(_) => None,
+        })
+    }
+
+    /// Set a setting, `None` is used for settings without value such as `enforce_for_root`.
+    ///
+    /// The value is validated by [`SettingInfo::validate`], so values that libpwquality
+    /// would clamp are rejected, and the setting must be supported. An existing
+    /// line of the setting is updated in place, otherwise the line is added after the
+    /// commented-out default of the setting or at the end of the file.
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<&mut Self> {
+        validate(key, value)?;
+
+        let value = value.map(String::from);
+        let last = self.lines.iter().rposition(|line| is_setting(line, key));
+
+        if let Some(last) = last {
+            if let Line::Setting { value: v, raw, .. } = &mut self.lines[last] {
+                *v = value;
+                *raw = None;
+            }
+
+            // drop the earlier lines that would be overridden anyway
+            let mut i = 0;
+            self.lines.retain(|line| {
+                let keep = i >= last || !is_setting(line, key);
+                i += 1;
+                keep
+            });
+        } else {
+            let line = Line::Setting {
+                key: key.to_string(),
+                value,
+                comment: None,
+                raw: None,
+            };
+            let default = self
+                .lines
+                .iter()
+                .rposition(|line| matches!(line, Line::Other(s) if is_commented_out(s, key)));
+
+            match default {
+                Some(i) => self.lines.insert(i + 1, line),
+                None => self.lines.push(line),
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Remove a setting, commented-out defaults are kept.
+    ///
+    /// Returns whether the setting was set.
+    pub fn unset(&mut self, key: &str) -> bool {
+        let len = self.lines.len();
+        self.lines.retain(|line| !is_setting(line, key));
+
+        self.lines.len() != len
+    }
+}
+
+impl std::fmt::Display for ConfigFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in &self.lines {
+            match line {
+                Line::Setting { raw: Some(raw), .. } => writeln!(f, "{raw}")?,
+                Line::Setting {
+                    key,
+                    value,
+                    comment,
+                    raw: None,
+                } => {
+                    write!(f, "{key}")?;
+                    if let Some(value) = value {
+                        write!(f, " = {value}")?;
+                    }
+                    if let Some(comment) = comment {
+                        write!(f, " {comment}")?;
+                    }
+                    writeln!(f)?;
+                }
+                Line::Other(s) => writeln!(f, "{s}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use crate::checklist::{contains, Input};
+use crate::{ErrorKind, PWQuality, Requirement, Status};
+
+/// Shortest word of the GECOS field that libpwquality looks for in passwords.
+const MIN_WORD_LENGTH: usize = 4;
+
+/// Account information that must not appear in the password.
+///
+/// The login and the local part of the email address are checked like the user name,
+/// according to `usercheck` and `usersubstr`. The other fields are split into words,
+/// each of which is checked like the words of the GECOS field, according to
+/// `gecoscheck`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserContext {
+    /// Login or user name.
+    pub login: Option<String>,
+    /// Full name displayed for the user.
+    pub display_name: Option<String>,
+    /// Email address.
+    pub email: Option<String>,
+    /// Phone number, in any format.
+    pub phone: Option<String>,
+    /// Organisation the user belongs to.
+    pub organization: Option<String>,
+    /// Any other information about the user, such as the city or the birth date.
+    pub extra: Vec<String>,
+}
+
+impl UserContext {
+    /// Create a context with only a login.
+    pub fn new(login: &str) -> Self {
+        Self {
+            login: Some(login.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// The names checked like the user name.
+    fn names(&self) -> Vec<&str> {
+        let local_part = self
+            .email
+            .as_deref()
+            .map(|email| email.split_once('@').map_or(email, |(local, _)| local));
+
+        self.login
+            .as_deref()
+            .into_iter()
+            .chain(local_part)
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    /// The lowercased words checked like the words of the GECOS field.
+    fn words(&self) -> Vec<String> {
+        let domain = self
+            .email
+            .as_deref()
+            .and_then(|email| email.split_once('@'))
+            .map(|(_, domain)| domain);
+        let texts = [
+            self.display_name.as_deref(),
+            self.organization.as_deref(),
+            domain,
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.extra.iter().map(String::as_str));
+
+        let mut words = texts
+            .flat_map(|text| text.split(|c: char| !c.is_alphanumeric()))
+            .map(str::to_ascii_lowercase)
+            .collect::<Vec<_>>();
+
+        // the digits of the phone number, in whole and by group
+        if let Some(phone) = &self.phone {
+            words.push(phone.chars().filter(char::is_ascii_digit).collect());
+            words.extend(
+                phone
+                    .split(|c: char| !c.is_ascii_digit())
+                    .map(str::to_string),
+            );
+        }
+
+        words.retain(|word| word.len() >= MIN_WORD_LENGTH);
+        words
+    }
+}
+
+/// Whether the lowercased password contains the word, forwards or backwards.
+///
+/// Like libpwquality, only ASCII letters are lowercased.
+pub(crate) fn contains_word(lower: &[u8], word: &str) -> bool {
+    let word = word.as_bytes().to_ascii_lowercase();
+    let reversed = word.iter().rev().copied().collect::<Vec<_>>();
+
+    word.len() >= MIN_WORD_LENGTH && (contains(lower, &word) || contains(lower, &reversed))
+}
+
+impl PWQuality {
+    /// Get the kind of error if the password contains information of the context.
+    pub(crate) fn check_context(&self, password: &str, context: &UserContext) -> Option<ErrorKind> {
+        #[cfg(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib"))]
+        let user_check = self.get_user_check();
+        #[cfg(not(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib")))]
+        let user_check = true;
+
+        #[cfg(any(
+            feature = "v1_4_5",
+            feature = "vendored",
+            feature = "vendored-cracklib"
+        ))]
+        let user_substr = self.get_user_substr();
+        #[cfg(not(any(
+            feature = "v1_4_5",
+            feature = "vendored",
+            feature = "vendored-cracklib"
+        )))]
+        let user_substr = 0;
+
+        if user_check {
+            let requirements = [
+                Requirement::NoUserName,
+                Requirement::UserSubstr(user_substr),
+            ];
+            let found = context.names().into_iter().any(|name| {
+                let input = Input::new(self, password, None, Some(name));
+                requirements
+                    .iter()
+                    .any(|requirement| requirement.status(&input) == Status::Unmet)
+            });
+
+            if found {
+                return Some(ErrorKind::UserCheck);
+            }
+        }
+
+        if self.get_gecos_check() {
+            let lower = password.as_bytes().to_ascii_lowercase();
+            if context
+                .words()
+                .iter()
+                .any(|word| contains_word(&lower, word))
+            {
+                return Some(ErrorKind::GecosCheck);
+            }
+        }
+
+        None
+    }
+}
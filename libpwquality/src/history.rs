@@ -0,0 +1,126 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+/// A bounded history of previous passwords, stored in a file as salted Argon2 hashes.
+///
+/// The file has one hash in the PHC string format per line, most recent first, and is
+/// only readable by its owner. Only exact reuse can be detected from the hashes, the
+/// plaintexts are needed by [`CheckContext::history`](crate::CheckContext::history) to
+/// detect near reuse.
+#[derive(Clone, Debug)]
+pub struct PasswordHistory {
+    path: PathBuf,
+    capacity: usize,
+    hashes: Vec<String>,
+}
+
+impl PasswordHistory {
+    /// Open the history stored in the file, keeping at most `capacity` passwords.
+    ///
+    /// The history is empty if the file doesn't exist, and the file is created when
+    /// the first password is added.
+    pub fn open(path: impl AsRef<Path>, capacity: usize) -> io::Result<Self> {
+        let mut hashes = match std::fs::read_to_string(&path) {
+            Ok(text) => text
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e),
+        };
+        hashes.truncate(capacity);
+
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            capacity,
+            hashes,
+        })
+    }
+
+    /// Get the maximum number of passwords kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get the number of passwords in the history.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Whether the history is empty.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Whether the password is one of the history.
+    ///
+    /// Malformed hashes are ignored.
+    pub fn contains(&self, password: &str) -> bool {
+        let argon2 = Argon2::default();
+
+        self.hashes.iter().any(|hash| {
+            PasswordHash::new(hash)
+                .is_ok_and(|hash| argon2.verify_password(password.as_bytes(), &hash).is_ok())
+        })
+    }
+
+    /// Add the password as the most recent one, forget the oldest one if the history
+    /// is full and save the history.
+    pub fn push(&mut self, password: &str) -> io::Result<()> {
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(io::Error::other)?;
+
+        self.hashes.insert(0, hash.to_string());
+        self.hashes.truncate(self.capacity);
+        self.save()
+    }
+
+    /// Write the history to a new temporary file in the same directory, renamed over
+    /// the file, so that it is never left half written.
+    fn save(&self) -> io::Result<()> {
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let name = self.path.file_name().unwrap_or_default().to_string_lossy();
+
+        // a new file that isn't a symbolic link, which another user could have planted
+        let (tmp, mut file) = loop {
+            let tmp = dir.join(format!(".{name}.{:016x}.tmp", OsRng.next_u64()));
+            match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .custom_flags(libc::O_NOFOLLOW)
+                .mode(0o600)
+                .open(&tmp)
+            {
+                Ok(file) => break (tmp, file),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        };
+
+        let result = self
+            .hashes
+            .iter()
+            .try_for_each(|hash| writeln!(file, "{hash}"))
+            .and_then(|_| file.sync_all())
+            .and_then(|_| std::fs::rename(&tmp, &self.path));
+        if result.is_err() {
+            let _ = std::fs::remove_file(&tmp);
+        }
+        result?;
+
+        // the rename is only durable once the directory is synced
+        File::open(dir)?.sync_all()
+    }
+}
@@ -0,0 +1,1061 @@
+use crate::{Aux, ErrorKind, PWQError};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The built-in English messages.
+///
+/// Plural forms are separated by `|` and selected by the `n` argument.
+const ENGLISH: &[(&str, &str)] = &[
+    (
+        "requirement.min_length",
+        "at least {n} character|at least {n} characters",
+    ),
+    (
+        "requirement.min_length.credits",
+        "at least {n} characters, where characters earning a credit count twice, \
+         so no fewer than {effective} characters",
+    ),
+    (
+        "requirement.min_digits",
+        "at least {n} digit|at least {n} digits",
+    ),
+    (
+        "requirement.min_uppercase",
+        "at least {n} uppercase letter|at least {n} uppercase letters",
+    ),
+    (
+        "requirement.min_lowercase",
+        "at least {n} lowercase letter|at least {n} lowercase letters",
+    ),
+    (
+        "requirement.min_other",
+        "at least {n} character that is not a letter or digit\
+         |at least {n} characters that are not letters or digits",
+    ),
+    (
+        "requirement.credit_digits",
+        "a digit counts as an extra character, for up to {n} digit\
+         |each digit counts as an extra character, for up to {n} digits",
+    ),
+    (
+        "requirement.credit_uppercase",
+        "an uppercase letter counts as an extra character, for up to {n} letter\
+         |each uppercase letter counts as an extra character, for up to {n} letters",
+    ),
+    (
+        "requirement.credit_lowercase",
+        "a lowercase letter counts as an extra character, for up to {n} letter\
+         |each lowercase letter counts as an extra character, for up to {n} letters",
+    ),
+    (
+        "requirement.credit_other",
+        "a character that is not a letter or digit counts as an extra character, \
+         for up to {n} character\
+         |each character that is not a letter or digit counts as an extra character, \
+         for up to {n} characters",
+    ),
+    (
+        "requirement.min_class",
+        "characters from at least {n} of the 4 classes: digits, uppercase letters, \
+         lowercase letters and other characters",
+    ),
+    (
+        "requirement.max_repeat",
+        "no more than {n} identical character in a row\
+         |no more than {n} identical characters in a row",
+    ),
+    (
+        "requirement.max_class_repeat",
+        "no more than {n} character of the same class in a row\
+         |no more than {n} characters of the same class in a row",
+    ),
+    (
+        "requirement.max_sequence",
+        "no sequence such as abcd or 4321 longer than {n} character\
+         |no sequence such as abcd or 4321 longer than {n} characters",
+    ),
+    (
+        "requirement.min_diff",
+        "must differ from your old password by at least {n} character\
+         |must differ from your old password by at least {n} characters",
+    ),
+    ("requirement.not_palindrome", "must not be a palindrome"),
+    (
+        "requirement.not_old_variant",
+        "must not be part of your old password repeated, or your old password with only case changes",
+    ),
+    (
+        "requirement.no_dictionary_word",
+        "must not be based on a dictionary word",
+    ),
+    (
+        "requirement.no_user_name",
+        "must not contain your user name",
+    ),
+    (
+        "requirement.user_substr",
+        "must not contain {n} consecutive character of your user name\
+         |must not contain {n} consecutive characters of your user name",
+    ),
+    (
+        "requirement.no_gecos",
+        "must not contain words from your full name or other account information",
+    ),
+    (
+        "requirement.no_bad_words",
+        "must not contain the word {words}|must not contain any of the words {words}",
+    ),
+    ("class.digits", "digit"),
+    ("class.uppercase", "uppercase letter"),
+    ("class.lowercase", "lowercase letter"),
+    ("class.other", "symbol"),
+    ("list.or", "{first} or {last}"),
+    (
+        "suggestion.add_characters",
+        "add {n} more character|add {n} more characters",
+    ),
+    ("suggestion.add_digits", "include {n} more digit|include {n} more digits"),
+    (
+        "suggestion.add_uppercase",
+        "include {n} more uppercase letter|include {n} more uppercase letters",
+    ),
+    (
+        "suggestion.add_lowercase",
+        "include {n} more lowercase letter|include {n} more lowercase letters",
+    ),
+    ("suggestion.add_other", "include {n} more symbol|include {n} more symbols"),
+    (
+        "suggestion.add_classes",
+        "include at least one {classes}\
+         |include characters of {n} more kinds, such as {classes}",
+    ),
+    (
+        "suggestion.avoid_repeat",
+        "avoid repeating characters as in '{part}'",
+    ),
+    (
+        "suggestion.avoid_class_run",
+        "mix other kinds of characters into '{part}'",
+    ),
+    ("suggestion.avoid_sequence", "avoid the sequence '{part}'"),
+    (
+        "suggestion.avoid_user_name",
+        "don't reuse parts of your user name",
+    ),
+    ("suggestion.avoid_word", "avoid the word '{word}'"),
+    (
+        "suggestion.avoid_palindrome",
+        "don't use a password that reads the same backwards",
+    ),
+    ("suggestion.avoid_old_password", "don't reuse your old password"),
+    (
+        "suggestion.change_more",
+        "change {n} more character from your old password\
+         |change {n} more characters from your old password",
+    ),
+    (
+        "similarity.same",
+        "the password is the same as the old one",
+    ),
+    (
+        "similarity.case_swapped",
+        "the password differs from the old one with case changes only",
+    ),
+    ("similarity.reversed", "the password is the old one reversed"),
+    (
+        "similarity.rotated",
+        "the password is a rotation of the old one",
+    ),
+    (
+        "similarity.edits",
+        "the password is only {n} edit away from the old one\
+         |the password is only {n} edits away from the old one",
+    ),
+    (
+        "similarity.common_substring",
+        "the password shares '{part}' with the old one, {percent}% of its length",
+    ),
+];
+
+/// Messages of libpwquality and cracklib, with their gettext message ids.
+///
+/// Translations of these message ids are imported from the `.po` and `.mo` files of
+/// libpwquality and cracklib. `%ld` becomes the `n` argument.
+const UPSTREAM: &[(&str, &str, Option<&str>)] = &[
+    ("error.fatal_failure", "Fatal failure", None),
+    ("error.integer", "Bad integer value", None),
+    (
+        "error.integer.setting",
+        "Bad integer value of setting",
+        None,
+    ),
+    (
+        "error.cfgfile_open",
+        "Opening the configuration file failed",
+        None,
+    ),
+    (
+        "error.cfgfile_malformed",
+        "The configuration file is malformed",
+        None,
+    ),
+    ("error.unknown_setting", "Unknown setting", None),
+    (
+        "error.non_int_setting",
+        "Setting is not of integer type",
+        None,
+    ),
+    (
+        "error.non_str_setting",
+        "Setting is not of string type",
+        None,
+    ),
+    ("error.mem_alloc", "Memory allocation error", None),
+    (
+        "error.mem_alloc.setting",
+        "Memory allocation error when setting",
+        None,
+    ),
+    (
+        "error.too_similar",
+        "The password is too similar to the old one",
+        None,
+    ),
+    (
+        "error.min_digits",
+        "The password contains too few digits",
+        None,
+    ),
+    (
+        "error.min_digits.count",
+        "The password contains less than %ld digit",
+        Some("The password contains less than %ld digits"),
+    ),
+    (
+        "error.min_uppers",
+        "The password contains too few uppercase letters",
+        None,
+    ),
+    (
+        "error.min_uppers.count",
+        "The password contains less than %ld uppercase letter",
+        Some("The password contains less than %ld uppercase letters"),
+    ),
+    (
+        "error.min_lowers",
+        "The password contains too few lowercase letters",
+        None,
+    ),
+    (
+        "error.min_lowers.count",
+        "The password contains less than %ld lowercase letter",
+        Some("The password contains less than %ld lowercase letters"),
+    ),
+    (
+        "error.min_others",
+        "The password contains too few non-alphanumeric characters",
+        None,
+    ),
+    (
+        "error.min_others.count",
+        "The password contains less than %ld non-alphanumeric character",
+        Some("The password contains less than %ld non-alphanumeric characters"),
+    ),
+    ("error.min_length", "The password is too short", None),
+    (
+        "error.min_length.count",
+        "The password is shorter than %ld character",
+        Some("The password is shorter than %ld characters"),
+    ),
+    ("error.palindrome", "The password is a palindrome", None),
+    (
+        "error.case_changes_only",
+        "The password differs with case changes only",
+        None,
+    ),
+    (
+        "error.rotated",
+        "The password is just rotated old one",
+        None,
+    ),
+    (
+        "error.min_classes",
+        "The password does not contain enough character classes",
+        None,
+    ),
+    (
+        "error.min_classes.count",
+        "The password contains less than %ld character class",
+        Some("The password contains less than %ld character classes"),
+    ),
+    (
+        "error.max_consecutive",
+        "The password contains too many same characters consecutively",
+        None,
+    ),
+    (
+        "error.max_consecutive.count",
+        "The password contains more than %ld same character consecutively",
+        Some("The password contains more than %ld same characters consecutively"),
+    ),
+    ("error.empty_password", "No password supplied", None),
+    (
+        "error.same_password",
+        "The password is the same as the old one",
+        None,
+    ),
+    (
+        "error.cracklib_check",
+        "The password fails the dictionary check",
+        None,
+    ),
+    (
+        "error.rng",
+        "Cannot obtain random numbers from the RNG device",
+        None,
+    ),
+    (
+        "error.generation_failed",
+        "Password generation failed - required entropy too low for settings",
+        None,
+    ),
+    (
+        "error.user_check",
+        "The password contains the user name in some form",
+        None,
+    ),
+    (
+        "error.gecos_check",
+        "The password contains words from the real name of the user in some form",
+        None,
+    ),
+    (
+        "error.max_class_repeat",
+        "The password contains too many characters of the same class consecutively",
+        None,
+    ),
+    (
+        "error.max_class_repeat.count",
+        "The password contains more than %ld character of the same class consecutively",
+        Some("The password contains more than %ld characters of the same class consecutively"),
+    ),
+    (
+        "error.bad_words",
+        "The password contains forbidden words in some form",
+        None,
+    ),
+    (
+        "error.max_sequence",
+        "The password contains too long of a monotonic character sequence",
+        None,
+    ),
+    (
+        "error.max_sequence.count",
+        "The password contains monotonic sequence longer than %ld character",
+        Some("The password contains monotonic sequence longer than %ld characters"),
+    ),
+    ("error.unknown", "Unknown error", None),
+    ("cracklib.way_too_short", "it is WAY too short", None),
+    ("cracklib.too_short", "it is too short", None),
+    (
+        "cracklib.not_enough_different",
+        "it does not contain enough DIFFERENT characters",
+        None,
+    ),
+    ("cracklib.all_whitespace", "it is all whitespace", None),
+    (
+        "cracklib.too_simplistic",
+        "it is too simplistic/systematic",
+        None,
+    ),
+    (
+        "cracklib.national_insurance",
+        "it looks like a National Insurance number.",
+        None,
+    ),
+    (
+        "cracklib.dictionary_word",
+        "it is based on a dictionary word",
+        None,
+    ),
+    (
+        "cracklib.reversed_dictionary_word",
+        "it is based on a (reversed) dictionary word",
+        None,
+    ),
+    (
+        "cracklib.dictionary_error",
+        "error loading dictionary",
+        None,
+    ),
+];
+
+/// Directory of the installed gettext catalogs.
+pub const LOCALE_DIR: &str = "/usr/share/locale";
+
+/// The gettext domains of the translations, libpwquality last for its plural rule to win.
+const DOMAINS: [&str; 2] = ["cracklib", "libpwquality"];
+
+/// The translations of libpwquality and cracklib bundled in the crate, by domain and
+/// language.
+///
+/// The `.po` files of the upstream projects go to `po/<domain>/<language>.po`, and each
+/// one is listed here with `include_str!`.
+const BUNDLED: &[(&str, &str, &str)] = &[
+    ("cracklib", "pl", include_str!("../po/cracklib/pl.po")),
+    (
+        "libpwquality",
+        "pl",
+        include_str!("../po/libpwquality/pl.po"),
+    ),
+];
+
+/// The languages to look up for a locale, like gettext.
+///
+/// The codeset and the modifier are ignored, and the language comes after the territory,
+/// so `pt_BR.UTF-8` gives `pt_BR` then `pt`.
+fn languages(locale: &str) -> Vec<String> {
+    let name = locale.split(['.', '@']).next().unwrap_or(locale);
+    let mut languages = vec![name.to_string()];
+    if let Some((language, _)) = name.split_once('_') {
+        languages.push(language.to_string());
+    }
+
+    languages
+}
+
+/// Convert a printf format of libpwquality to a template.
+fn from_printf(format: &str) -> String {
+    format
+        .replace("%ld", "{n}")
+        .replace("%d", "{n}")
+        .replace("%s", "{aux}")
+}
+
+/// Rule selecting the index of the plural form for a count.
+type PluralRule = fn(i64) -> usize;
+
+/// Select the plural form for English and most Germanic and Romance languages.
+fn english_plural(n: i64) -> usize {
+    usize::from(n != 1)
+}
+
+/// Plural expression of a gettext `Plural-Forms` header, in C syntax.
+#[derive(Clone, Debug)]
+enum Expr {
+    N,
+    Number(i64),
+    Not(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Condition(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+/// Binary operators by increasing precedence.
+const OPERATORS: &[&[&str]] = &[
+    &["||"],
+    &["&&"],
+    &["==", "!="],
+    &["<=", ">=", "<", ">"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+struct ExprParser<'a> {
+    rest: &'a str,
+}
+
+impl ExprParser<'_> {
+    fn eat(&mut self, token: &str) -> bool {
+        self.rest = self.rest.trim_start();
+        match self.rest.strip_prefix(token) {
+            // don't take `<` for `<=` nor `!` for `!=`
+            Some(rest)
+                if !(token.len() == 1 && "<>!=".contains(token) && rest.starts_with('=')) =>
+            {
+                self.rest = rest;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn condition(&mut self) -> Result<Expr, String> {
+        let condition = self.binary(0)?;
+        if !self.eat("?") {
+            return Ok(condition);
+        }
+
+        let then = self.condition()?;
+        if !self.eat(":") {
+            return Err("missing ':'".to_string());
+        }
+        let otherwise = self.condition()?;
+
+        Ok(Expr::Condition(
+            Box::new(condition),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr, String> {
+        if level == OPERATORS.len() {
+            return self.unary();
+        }
+
+        let mut lhs = self.binary(level + 1)?;
+        while let Some(op) = OPERATORS[level].iter().find(|op| self.eat(op)) {
+            let rhs = self.binary(level + 1)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let expr = self.condition()?;
+            return match self.eat(")") {
+                true => Ok(expr),
+                false => Err("missing ')'".to_string()),
+            };
+        }
+        if self.eat("n") {
+            return Ok(Expr::N);
+        }
+
+        let digits = self
+            .rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len());
+        let (number, rest) = self.rest.split_at(digits);
+        self.rest = rest;
+
+        number
+            .parse()
+            .map(Expr::Number)
+            .map_err(|_| format!("unexpected '{}'", self.rest))
+    }
+}
+
+impl Expr {
+    fn parse(s: &str) -> Result<Self, String> {
+        let mut parser = ExprParser { rest: s };
+        let expr = parser.condition()?;
+
+        match parser.rest.trim() {
+            "" => Ok(expr),
+            rest => Err(format!("unexpected '{rest}'")),
+        }
+    }
+
+    fn eval(&self, n: i64) -> i64 {
+        match self {
+            Expr::N => n,
+            Expr::Number(v) => *v,
+            Expr::Not(e) => i64::from(e.eval(n) == 0),
+            Expr::Condition(c, a, b) => match c.eval(n) {
+                0 => b.eval(n),
+                _ => a.eval(n),
+            },
+            Expr::Binary(op, a, b) => {
+                let (a, b) = (a.eval(n), b.eval(n));
+                match *op {
+                    "||" => i64::from(a != 0 || b != 0),
+                    "&&" => i64::from(a != 0 && b != 0),
+                    "==" => i64::from(a == b),
+                    "!=" => i64::from(a != b),
+                    "<=" => i64::from(a <= b),
+                    ">=" => i64::from(a >= b),
+                    "<" => i64::from(a < b),
+                    ">" => i64::from(a > b),
+                    "+" => a.wrapping_add(b),
+                    "-" => a.wrapping_sub(b),
+                    "*" => a.wrapping_mul(b),
+                    "/" => a.checked_div(b).unwrap_or(0),
+                    _ => a.checked_rem(b).unwrap_or(0),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Plural {
+    Rule(PluralRule),
+    Expr(Expr),
+}
+
+impl Plural {
+    fn select(&self, n: i64) -> usize {
+        match self {
+            Plural::Rule(rule) => rule(n),
+            Plural::Expr(expr) => usize::try_from(expr.eval(n)).unwrap_or(0),
+        }
+    }
+}
+
+const ENGLISH_PLURAL: Plural = Plural::Rule(english_plural);
+
+/// Unescape a C string literal of a `.po` file.
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(c) => out.push(c),
+            None => {}
+        }
+    }
+
+    out
+}
+
+/// Read a native or byte swapped 32-bit integer of a `.mo` file.
+fn read_u32(data: &[u8], offset: usize, swapped: bool) -> Result<u32, String> {
+    let bytes: [u8; 4] = data
+        .get(offset..offset + 4)
+        .and_then(|b| b.try_into().ok())
+        .ok_or("truncated file")?;
+
+    Ok(match swapped {
+        false => u32::from_le_bytes(bytes),
+        true => u32::from_be_bytes(bytes),
+    })
+}
+
+/// A catalog of localized messages.
+///
+/// Messages are templates with `{name}` placeholders. A template may have several plural
+/// forms separated by `|`, one of which is selected from the `n` argument by the plural
+/// rule of the catalog. Messages missing from the catalog fall back to English.
+#[derive(Clone, Debug)]
+pub struct Catalog {
+    locale: String,
+    messages: HashMap<String, String>,
+    plural: Plural,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl Catalog {
+    /// Create an empty catalog for the locale, using the English plural rule.
+    pub fn new(locale: &str) -> Self {
+        Self {
+            locale: locale.to_string(),
+            messages: HashMap::new(),
+            plural: ENGLISH_PLURAL,
+        }
+    }
+
+    /// Load the installed libpwquality and cracklib translations for the locale from
+    /// [`LOCALE_DIR`], or the [`bundled`](Self::bundled) ones if none is installed.
+    ///
+    /// See [`load`](Self::load).
+    pub fn installed(locale: &str) -> Result<Self, String> {
+        Self::load(LOCALE_DIR, locale).or_else(|e| Self::bundled(locale).map_err(|_| e))
+    }
+
+    /// Parse the libpwquality and cracklib translations for the locale bundled in the
+    /// crate.
+    ///
+    /// The languages are looked up like [`load`](Self::load) does. It is an error if the
+    /// crate bundles no translation for the locale.
+    pub fn bundled(locale: &str) -> Result<Self, String> {
+        let languages = languages(locale);
+        let mut catalog = Self::new(locale);
+        let mut found = false;
+
+        for domain in DOMAINS {
+            let po = languages.iter().find_map(|language| {
+                BUNDLED
+                    .iter()
+                    .find(|(d, l, _)| *d == domain && l == language)
+            });
+
+            if let Some((domain, language, text)) = po {
+                catalog
+                    .import_po(text)
+                    .map_err(|e| format!("{domain}/{language}.po: {e}"))?;
+                found = true;
+            }
+        }
+
+        match found {
+            true => Ok(catalog),
+            false => Err(format!(
+                "no bundled libpwquality or cracklib catalog for {locale}"
+            )),
+        }
+    }
+
+    /// Load the libpwquality and cracklib translations for the locale from a gettext
+    /// locale directory, such as `/usr/share/locale`.
+    ///
+    /// Like gettext, the codeset and the modifier of the locale are ignored, and the
+    /// catalogs of the language are used when there are none for the territory, so
+    /// `pt_BR.UTF-8` looks for `pt_BR` then `pt`. It is an error if neither
+    /// `libpwquality.mo` nor `cracklib.mo` is found.
+    pub fn load(dir: impl AsRef<Path>, locale: &str) -> Result<Self, String> {
+        let candidates = languages(locale);
+        let mut catalog = Self::new(locale);
+        let mut found = false;
+
+        for domain in DOMAINS {
+            let path = candidates
+                .iter()
+                .map(|c| {
+                    dir.as_ref()
+                        .join(c)
+                        .join("LC_MESSAGES")
+                        .join(format!("{domain}.mo"))
+                })
+                .find(|path| path.exists());
+
+            if let Some(path) = path {
+                let data = std::fs::read(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+                catalog
+                    .import_mo(&data)
+                    .map_err(|e| format!("{}: {e}", path.display()))?;
+                found = true;
+            }
+        }
+
+        match found {
+            true => Ok(catalog),
+            false => Err(format!(
+                "no libpwquality or cracklib catalog for {locale} in {}",
+                dir.as_ref().display()
+            )),
+        }
+    }
+
+    /// Parse the translations of libpwquality or cracklib from a gettext `.po` file.
+    ///
+    /// Only the messages of libpwquality and cracklib are imported. Fuzzy entries are
+    /// skipped, and the plural rule is taken from the `Plural-Forms` header.
+    pub fn from_po(locale: &str, text: &str) -> Result<Self, String> {
+        let mut catalog = Self::new(locale);
+        catalog.import_po(text)?;
+
+        Ok(catalog)
+    }
+
+    /// Parse the translations of libpwquality or cracklib from a compiled gettext `.mo`
+    /// file.
+    ///
+    /// See [`from_po`](Self::from_po).
+    pub fn from_mo(locale: &str, data: &[u8]) -> Result<Self, String> {
+        let mut catalog = Self::new(locale);
+        catalog.import_mo(data)?;
+
+        Ok(catalog)
+    }
+
+    fn import_po(&mut self, text: &str) -> Result<(), String> {
+        #[derive(Default)]
+        struct Entry {
+            fuzzy: bool,
+            context: bool,
+            msgid: String,
+            msgstr: Vec<String>,
+        }
+
+        /// The string continued by the following lines.
+        enum Field {
+            Ignored,
+            Msgid,
+            Msgstr,
+        }
+
+        let mut entries = vec![];
+        let mut entry = Entry::default();
+        let mut field = Field::Ignored;
+
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let error = || format!("line {}: malformed entry", n + 1);
+
+            // a comment or a keyword other than msgstr starts a new entry
+            let new_entry = line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("msgctxt")
+                || line.starts_with("msgid ");
+            if new_entry && !entry.msgstr.is_empty() {
+                entries.push(std::mem::take(&mut entry));
+            }
+
+            if line.is_empty() || line.starts_with('#') {
+                entry.fuzzy |= line.starts_with("#,") && line.contains("fuzzy");
+                field = Field::Ignored;
+                continue;
+            }
+
+            let (keyword, string) = match line.find('"') {
+                Some(i) => (line[..i].trim(), &line[i..]),
+                None => return Err(error()),
+            };
+            let string = string
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .map(unescape)
+                .ok_or_else(error)?;
+
+            match keyword {
+                "" => {}
+                "msgctxt" => {
+                    entry.context = true;
+                    field = Field::Ignored;
+                }
+                "msgid_plural" => field = Field::Ignored,
+                "msgid" => field = Field::Msgid,
+                _ if keyword == "msgstr" || keyword.starts_with("msgstr[") => {
+                    entry.msgstr.push(String::new());
+                    field = Field::Msgstr;
+                }
+                _ => return Err(error()),
+            }
+
+            match field {
+                Field::Ignored => {}
+                Field::Msgid => entry.msgid.push_str(&string),
+                Field::Msgstr => entry.msgstr.last_mut().ok_or_else(error)?.push_str(&string),
+            }
+        }
+        entries.push(entry);
+
+        for entry in entries {
+            if !entry.fuzzy && !entry.context {
+                self.import(&entry.msgid, &entry.msgstr)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn import_mo(&mut self, data: &[u8]) -> Result<(), String> {
+        let swapped = match read_u32(data, 0, false)? {
+            0x950412de => false,
+            0xde120495 => true,
+            _ => return Err("not a gettext catalog".to_string()),
+        };
+        let count = read_u32(data, 8, swapped)? as usize;
+        let originals = read_u32(data, 12, swapped)? as usize;
+        let translations = read_u32(data, 16, swapped)? as usize;
+
+        let string = |table: usize, i: usize| -> Result<String, String> {
+            let length = read_u32(data, table + i * 8, swapped)? as usize;
+            let offset = read_u32(data, table + i * 8 + 4, swapped)? as usize;
+            let bytes = data.get(offset..offset + length).ok_or("truncated file")?;
+
+            Ok(String::from_utf8_lossy(bytes).to_string())
+        };
+
+        for i in 0..count {
+            let original = string(originals, i)?;
+            // entries with a context are stored as `context\x04msgid`
+            if original.contains('\x04') {
+                continue;
+            }
+
+            let msgid = original.split('\0').next().unwrap_or_default();
+            let msgstr = string(translations, i)?
+                .split('\0')
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            self.import(msgid, &msgstr)?;
+        }
+
+        Ok(())
+    }
+
+    /// Import a gettext translation, ignoring the messages unknown to the catalog.
+    fn import(&mut self, msgid: &str, msgstr: &[String]) -> Result<(), String> {
+        if msgid.is_empty() {
+            let header = msgstr.first().map(String::as_str).unwrap_or_default();
+            let plural = header
+                .lines()
+                .find_map(|line| line.strip_prefix("Plural-Forms:"))
+                .and_then(|forms| {
+                    forms
+                        .split(';')
+                        .find_map(|s| s.trim().strip_prefix("plural="))
+                });
+
+            if let Some(plural) = plural {
+                self.plural =
+                    Plural::Expr(Expr::parse(plural).map_err(|e| format!("Plural-Forms: {e}"))?);
+            }
+            return Ok(());
+        }
+
+        let Some((id, ..)) = UPSTREAM.iter().find(|(_, i, _)| *i == msgid) else {
+            return Ok(());
+        };
+        if msgstr.iter().all(String::is_empty) {
+            return Ok(());
+        }
+
+        let template = msgstr
+            .iter()
+            .map(|s| from_printf(s))
+            .collect::<Vec<_>>()
+            .join("|");
+        self.insert(id, &template);
+
+        Ok(())
+    }
+
+    /// Parse a catalog from `id = template` lines, blank lines and lines starting with
+    /// `#` are ignored.
+    pub fn parse(locale: &str, text: &str) -> Result<Self, String> {
+        let mut catalog = Self::new(locale);
+
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (id, template) = line
+                .split_once('=')
+                .ok_or(format!("line {}: missing '='", n + 1))?;
+            catalog.insert(id.trim(), template.trim());
+        }
+
+        Ok(catalog)
+    }
+
+    /// Get the locale of the catalog.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Set the rule selecting the index of the plural form for a count.
+    pub fn plural_rule(&mut self, rule: PluralRule) -> &mut Self {
+        self.plural = Plural::Rule(rule);
+        self
+    }
+
+    /// Add or replace a message.
+    pub fn insert(&mut self, id: &str, template: &str) -> &mut Self {
+        self.messages.insert(id.to_string(), template.to_string());
+        self
+    }
+
+    /// Get the template of a message, falling back to English.
+    fn template(&self, id: &str) -> Option<(Cow<'_, str>, &Plural)> {
+        if let Some(template) = self.messages.get(id) {
+            return Some((Cow::Borrowed(template), &self.plural));
+        }
+
+        let english = ENGLISH
+            .iter()
+            .find(|(i, _)| *i == id)
+            .map(|(_, template)| Cow::Borrowed(*template));
+        let upstream = || {
+            UPSTREAM
+                .iter()
+                .find(|(i, ..)| *i == id)
+                .map(|(_, msgid, plural)| {
+                    let forms = [Some(*msgid), *plural];
+                    Cow::Owned(
+                        forms
+                            .iter()
+                            .flatten()
+                            .map(|s| from_printf(s))
+                            .collect::<Vec<_>>()
+                            .join("|"),
+                    )
+                })
+        };
+
+        english.or_else(upstream).map(|t| (t, &ENGLISH_PLURAL))
+    }
+
+    /// Format a message with the given arguments.
+    ///
+    /// The message id itself is returned if the message is unknown.
+    pub fn format(&self, id: &str, args: &[(&str, String)]) -> String {
+        let Some((template, plural)) = self.template(id) else {
+            return id.to_string();
+        };
+
+        let forms = template.split('|').collect::<Vec<_>>();
+        let count = args
+            .iter()
+            .find(|(name, _)| *name == "n")
+            .and_then(|(_, value)| value.parse::<i64>().ok());
+        let form = count.map_or(0, |n| plural.select(n)).min(forms.len() - 1);
+
+        args.iter()
+            .fold(forms[form].to_string(), |s, (name, value)| {
+                s.replace(&format!("{{{name}}}"), value)
+            })
+    }
+}
+
+impl PWQError {
+    /// Describe the error with the messages of the catalog.
+    ///
+    /// Unlike the [`Display`](std::fmt::Display) implementation, which uses the process
+    /// locale if libpwquality was built with gettext, the error is rendered in the locale
+    /// of the catalog. The reasons given by cracklib are translated if they are in
+    /// English, that is if cracklib runs in the C locale.
+    pub fn text(&self, catalog: &Catalog) -> String {
+        // the messages of custom rules are kept in English without a translation
+        if let Aux::Message { id, text } = &self.aux {
+            return match catalog.template(id) {
+                Some(_) => catalog.format(id, &[]),
+                None => text.clone(),
+            };
+        }
+
+        let id = format!("error.{}", self.kind().name());
+
+        if let Aux::Similarity(reason) = &self.aux {
+            return format!("{} - {}", catalog.format(&id, &[]), reason.text(catalog));
+        }
+
+        if let Some(n) = self.aux_int() {
+            return catalog.format(&format!("{id}.count"), &[("n", n.to_string())]);
+        }
+
+        let Some(aux) = self.aux_str() else {
+            return catalog.format(&id, &[]);
+        };
+
+        match self.kind() {
+            // the matched word is a parameter, like in the message of libpwquality
+            ErrorKind::BadWords => catalog.format(&id, &[]),
+            ErrorKind::Integer | ErrorKind::MemAlloc => {
+                format!("{} - {aux}", catalog.format(&format!("{id}.setting"), &[]))
+            }
+            ErrorKind::CracklibCheck => {
+                let reason = UPSTREAM
+                    .iter()
+                    .find(|(i, msgid, _)| i.starts_with("cracklib.") && *msgid == aux)
+                    .map_or(aux.to_string(), |(i, ..)| catalog.format(i, &[]));
+
+                format!("{} - {reason}", catalog.format(&id, &[]))
+            }
+            _ => format!("{} - {aux}", catalog.format(&id, &[])),
+        }
+    }
+}
@@ -24,139 +24,775 @@
 
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+mod audit;
+mod check;
+mod checklist;
+mod conf;
+mod context;
+#[cfg(feature = "history")]
+mod history;
+mod i18n;
+mod json;
+mod lint;
+mod migrate;
+mod passwd;
+#[cfg(feature = "regex")]
+mod pattern;
+mod preset;
+mod registry;
+mod requirement;
+mod rule;
+mod schema;
+#[cfg(feature = "serde")]
+mod serialize;
+mod similarity;
+mod suggest;
+
+pub use audit::{AuditReport, Finding, Standard, Status};
+pub use check::CheckContext;
+pub use checklist::ChecklistItem;
+pub use conf::{ConfigFile, DROP_IN_DIR};
+pub use context::UserContext;
+#[cfg(feature = "history")]
+pub use history::PasswordHistory;
+pub use i18n::{Catalog, LOCALE_DIR};
+pub use lint::{lint_config, Diagnostic, LintReport, Severity};
+pub use migrate::{migrate_pam, Location, Migration, PamLineChange, Untranslated};
+pub use passwd::{PasswdEntry, PASSWD_FILE};
+#[cfg(feature = "regex")]
+pub use pattern::DenyPattern;
+pub use preset::Preset;
+pub use registry::{SettingInfo, ValueType};
+pub use requirement::{CharClass, Requirement};
+pub use rule::Rule;
+pub use schema::json_schema;
+pub use similarity::{Similarity, SimilarityReason};
+pub use suggest::{Advice, Suggestion};
+
 use libpwquality_sys as sys;
 use paste::paste;
 use std::os::raw::{c_char, c_int, c_void};
 use std::{
-    ffi::{CStr, CString},
+    borrow::Cow,
+    cell::RefCell,
+    ffi::{CStr, CString, OsStr},
+    os::unix::ffi::OsStrExt,
     path::Path,
     ptr::{null, null_mut},
+    rc::Rc,
 };
 
+macro_rules! define_getseters {
+    ([true] $($rest:tt)*) => {};
+    ([] Int, $func:ident, $setting:ident, $key:literal, $description:literal $(, $feature:literal)?) => {
+        paste! {
+            $(#[cfg(any(feature = $feature, feature = "vendored", feature = "vendored-cracklib"))])?
+            #[doc = concat!(" Get the `", $key, "` setting.\n\n ", $description)]
+            pub fn [<get_ $func>] (&self) -> i32 {
+                self.get_int_value($crate::Setting::$setting)
+            }
+
+            $(#[cfg(any(feature = $feature, feature = "vendored", feature = "vendored-cracklib"))])?
+            #[doc = concat!(" Set the `", $key, "` setting.\n\n ", $description)]
+            pub fn $func(&self, value: i32) -> &Self {
+                self.set_int_value($crate::Setting::$setting, value)
+            }
+        }
+    };
+    ([] Bool, $func:ident, $setting:ident, $key:literal, $description:literal $(, $feature:literal)?) => {
+        paste! {
+            $(#[cfg(any(feature = $feature, feature = "vendored", feature = "vendored-cracklib"))])?
+            #[doc = concat!(" Get the `", $key, "` setting.\n\n ", $description)]
+            pub fn [<get_ $func>] (&self) -> bool {
+                self.get_int_value($crate::Setting::$setting) != 0
+            }
+
+            $(#[cfg(any(feature = $feature, feature = "vendored", feature = "vendored-cracklib"))])?
+            #[doc = concat!(" Set the `", $key, "` setting.\n\n ", $description)]
+            pub fn $func(&self, value: bool) -> &Self {
+                self.set_int_value($crate::Setting::$setting, i32::from(value))
+            }
+        }
+    };
+    // the string settings have their own accessors
+    ([] $value_type:ident, $($rest:tt)*) => {};
+}
+
 macro_rules! define_settings {
-    ($($(#[$meta:meta])? $setting:ident),* $(,)?) => {
+    (
+        $(
+            $setting:ident => $key:literal {
+                accessor: $accessor:ident,
+                value: $value_type:ident = $default:literal,
+                range: $range:expr,
+                negative: $negative:expr,
+                description: $description:literal,
+                since: $since:literal $(($feature:literal))?,
+                $(bare: $bare:literal,)?
+                $(manual: $manual:tt,)?
+            }
+        ),* $(,)?
+    ) => {
         paste! {
             /// `PWQuality` Setting.
-            #[derive(Copy, Clone, Debug)]
+            #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
             #[non_exhaustive]
-            enum Setting {
+            pub enum Setting {
                 $(
-                    $(#[$meta])?
+                    $(#[cfg(any(feature = $feature, feature = "vendored", feature = "vendored-cracklib"))])?
+                    #[doc = concat!(" The `", $key, "` setting.")]
                     $setting = sys::[<PWQ_SETTING_ $setting:snake:upper>] as isize,
                 )*
             }
+
+            impl Setting {
+                /// All the settings supported by the enabled features.
+                pub const ALL: &'static [Setting] = &[
+                    $(
+                        $(#[cfg(any(feature = $feature, feature = "vendored", feature = "vendored-cracklib"))])?
+                        Setting::$setting,
+                    )*
+                ];
+
+                /// Get the key of the setting in the configuration file.
+                pub fn key(self) -> &'static str {
+                    match self {
+                        $(
+                            $(#[cfg(any(feature = $feature, feature = "vendored", feature = "vendored-cracklib"))])?
+                            Setting::$setting => $key,
+                        )*
+                    }
+                }
+
+                /// Get the description of the setting.
+                pub fn info(self) -> &'static SettingInfo {
+                    match self {
+                        $(
+                            $(#[cfg(any(feature = $feature, feature = "vendored", feature = "vendored-cracklib"))])?
+                            Setting::$setting => &[<$setting:snake:upper _INFO>],
+                        )*
+                    }
+                }
+            }
+
+            $(
+                const [<$setting:snake:upper _INFO>]: SettingInfo = SettingInfo {
+                    key: $key,
+                    accessor: stringify!($accessor),
+                    value_type: ValueType::$value_type,
+                    bare: define_settings!(@flag $($bare)?),
+                    default: $default,
+                    range: $range,
+                    negative: $negative,
+                    description: $description,
+                    since: $since,
+                    supported: define_settings!(@supported $($feature)?),
+                };
+            )*
+
+            impl SettingInfo {
+                /// All the settings known to this crate, including the unsupported ones.
+                const ALL: &'static [SettingInfo] = &[$([<$setting:snake:upper _INFO>]),*];
+            }
+
+            impl PWQuality {
+                $(
+                    define_getseters! {
+                        [$($manual)?] $value_type, $accessor, $setting, $key, $description
+                        $(, $feature)?
+                    }
+                )*
+            }
         }
     };
+    (@flag) => {
+        false
+    };
+    (@flag $flag:literal) => {
+        $flag
+    };
+    (@supported) => {
+        true
+    };
+    (@supported $feature:literal) => {
+        cfg!(any(
+            feature = $feature,
+            feature = "vendored",
+            feature = "vendored-cracklib"
+        ))
+    };
 }
 
 define_settings! {
-    DiffOk,
-    MinLength,
-    DigCredit,
-    UpCredit,
-    LowCredit,
-    OthCredit,
-    MinClass,
-    MaxRepeat,
-    DictPath,
-    MaxClassRepeat,
-    GecosCheck,
-    BadWords,
-    #[cfg(any(feature = "v1_2", feature = "vendored", feature = "vendored-cracklib"))]
-    MaxSequence,
-    #[cfg(any(feature = "v1_3", feature = "vendored", feature = "vendored-cracklib"))]
-    DictCheck,
-    #[cfg(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib"))]
-    UserCheck,
-    #[cfg(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib"))]
-    Enforcing,
-    #[cfg(any(feature = "v1_4_1", feature = "vendored", feature = "vendored-cracklib"))]
-    RetryTimes,
-    #[cfg(any(feature = "v1_4_1", feature = "vendored", feature = "vendored-cracklib"))]
-    EnforceRoot,
-    #[cfg(any(feature = "v1_4_1", feature = "vendored", feature = "vendored-cracklib"))]
-    LocalUsers,
-    #[cfg(any(feature = "v1_4_3", feature = "vendored", feature = "vendored-cracklib"))]
-    UserSubstr,
+    DiffOk => "difok" {
+        accessor: min_diff,
+        value: Int = "1",
+        range: Some((0, i32::MAX)),
+        negative: None,
+        description: "The minimum number of characters in the new password that must not be present in the old password.",
+        since: "1.0",
+    },
+    MinLength => "minlen" {
+        accessor: min_length,
+        value: Int = "8",
+        range: Some((6, i32::MAX)),
+        negative: None,
+        description: "The minimum acceptable size for the new password.",
+        since: "1.0",
+    },
+    DigCredit => "dcredit" {
+        accessor: digit_credit,
+        value: Int = "0",
+        range: Some((i32::MIN, i32::MAX)),
+        negative: CREDIT,
+        description: "The maximum credit for having digits in the new password.",
+        since: "1.0",
+    },
+    UpCredit => "ucredit" {
+        accessor: uppercase_credit,
+        value: Int = "0",
+        range: Some((i32::MIN, i32::MAX)),
+        negative: CREDIT,
+        description: "The maximum credit for having uppercase characters in the new password.",
+        since: "1.0",
+    },
+    LowCredit => "lcredit" {
+        accessor: lowercase_credit,
+        value: Int = "0",
+        range: Some((i32::MIN, i32::MAX)),
+        negative: CREDIT,
+        description: "The maximum credit for having lowercase characters in the new password.",
+        since: "1.0",
+    },
+    OthCredit => "ocredit" {
+        accessor: other_credit,
+        value: Int = "0",
+        range: Some((i32::MIN, i32::MAX)),
+        negative: CREDIT,
+        description: "The maximum credit for having other characters in the new password.",
+        since: "1.0",
+    },
+    MinClass => "minclass" {
+        accessor: min_class,
+        value: Int = "0",
+        range: Some((0, 4)),
+        negative: None,
+        description: "The minimum number of required classes of characters for the new password.",
+        since: "1.0",
+    },
+    MaxRepeat => "maxrepeat" {
+        accessor: max_repeat,
+        value: Int = "0",
+        range: Some((0, i32::MAX)),
+        negative: None,
+        description: "The maximum number of allowed same consecutive characters in the new password.",
+        since: "1.0",
+    },
+    DictPath => "dictpath" {
+        accessor: dict_path,
+        value: Str = "",
+        range: None,
+        negative: None,
+        description: "The path to the cracklib dictionaries.",
+        since: "1.0",
+    },
+    MaxClassRepeat => "maxclassrepeat" {
+        accessor: max_class_repeat,
+        value: Int = "0",
+        range: Some((0, i32::MAX)),
+        negative: None,
+        description: "The maximum number of allowed consecutive characters of the same class in the new password.",
+        since: "1.0",
+    },
+    GecosCheck => "gecoscheck" {
+        accessor: gecos_check,
+        value: Bool = "0",
+        range: None,
+        negative: None,
+        description: "Whether to perform the passwd GECOS field check.",
+        since: "1.0",
+    },
+    BadWords => "badwords" {
+        accessor: bad_words,
+        value: List = "",
+        range: None,
+        negative: None,
+        description: "The list of words more than 3 characters long that are forbidden.",
+        since: "1.0",
+    },
+    MaxSequence => "maxsequence" {
+        accessor: max_sequence,
+        value: Int = "0",
+        range: Some((0, i32::MAX)),
+        negative: None,
+        description: "The maximum length of monotonic character sequences in the new password.",
+        since: "1.2" ("v1_2"),
+    },
+    DictCheck => "dictcheck" {
+        accessor: dict_check,
+        value: Bool = "1",
+        range: None,
+        negative: None,
+        description: "Whether to perform the dictionary check.",
+        since: "1.3" ("v1_3"),
+    },
+    UserCheck => "usercheck" {
+        accessor: user_check,
+        value: Bool = "1",
+        range: None,
+        negative: None,
+        description: "Whether to perform the user name check.",
+        since: "1.4" ("v1_4"),
+    },
+    Enforcing => "enforcing" {
+        accessor: enforcing,
+        value: Bool = "1",
+        range: None,
+        negative: None,
+        description: "Whether the check is enforced.",
+        since: "1.4" ("v1_4"),
+    },
+    RetryTimes => "retry" {
+        accessor: retry_times,
+        value: Int = "1",
+        range: Some((1, i32::MAX)),
+        negative: None,
+        description: "Maximum retries for the password change should be allowed.",
+        since: "1.4.1" ("v1_4_1"),
+    },
+    EnforceRoot => "enforce_for_root" {
+        accessor: enforce_for_root,
+        value: Bool = "0",
+        range: None,
+        negative: None,
+        description: "Whether the check is enforced for root.",
+        since: "1.4.1" ("v1_4_1"),
+        bare: true,
+    },
+    LocalUsers => "local_users_only" {
+        accessor: local_users_only,
+        value: Bool = "0",
+        range: None,
+        negative: None,
+        description: "Whether to check local users only.",
+        since: "1.4.1" ("v1_4_1"),
+        bare: true,
+    },
+    // libpwquality can only get the value since 1.4.5
+    UserSubstr => "usersubstr" {
+        accessor: user_substr,
+        value: Int = "0",
+        range: Some((0, i32::MAX)),
+        negative: None,
+        description: "The length of substrings of the user name to check.",
+        since: "1.4.3" ("v1_4_3"),
+        manual: true,
+    },
+}
+
+/// Meaning of negative credits.
+const CREDIT: Option<&str> = Some(
+    "the absolute value is the minimum number of characters of the class \
+     that the new password must contain",
+);
+
+impl Setting {
+    /// Iterate over the keys of all the settings supported by the enabled features.
+    pub fn keys() -> impl Iterator<Item = &'static str> {
+        Self::ALL.iter().map(|s| s.key())
+    }
+
+    /// Whether the setting holds a string rather than an integer.
+    pub fn is_str(self) -> bool {
+        matches!(self.info().value_type, ValueType::Str | ValueType::List)
+    }
+}
+
+impl std::fmt::Display for Setting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl std::str::FromStr for Setting {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|setting| setting.key() == s)
+            .ok_or_else(|| format!("unknown setting: {s}"))
+    }
+}
+
+macro_rules! define_errors {
+    ($($kind:ident => $code:literal),* $(,)?) => {
+        paste! {
+            /// Kind of a `PWQuality` error.
+            #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+            #[non_exhaustive]
+            pub enum ErrorKind {
+                $(
+                    #[doc = concat!(" `PWQ_ERROR_", stringify!([<$kind:snake:upper>]), "`.")]
+                    $kind,
+                )*
+                /// An error code unknown to this crate.
+                Unknown(i32),
+                /// An error reported by a custom [`Rule`], with its stable code.
+                Rule(Cow<'static, str>),
+            }
+
+            impl ErrorKind {
+                /// All the known kinds.
+                pub const ALL: &'static [ErrorKind] = &[$(ErrorKind::$kind),*];
+
+                /// Get the kind of a libpwquality error code.
+                pub fn from_code(code: i32) -> Self {
+                    match code {
+                        $(
+                            c if c == sys::[<PWQ_ERROR_ $kind:snake:upper>] => ErrorKind::$kind,
+                        )*
+                        c => ErrorKind::Unknown(c),
+                    }
+                }
+
+                /// Get the libpwquality error code.
+                ///
+                /// libpwquality has no code for the errors of custom rules, which are
+                /// reported as `PWQ_ERROR_FATAL_FAILURE`.
+                pub fn code(&self) -> i32 {
+                    match self {
+                        $(
+                            ErrorKind::$kind => sys::[<PWQ_ERROR_ $kind:snake:upper>],
+                        )*
+                        ErrorKind::Unknown(c) => *c,
+                        ErrorKind::Rule(_) => sys::PWQ_ERROR_FATAL_FAILURE,
+                    }
+                }
+
+                /// Get the stable code of the kind, such as `too_short`.
+                ///
+                /// Unlike the messages, the codes don't change across libpwquality
+                /// versions or locales and are meant for machine consumption.
+                pub fn stable_code(&self) -> &str {
+                    match self {
+                        $(
+                            ErrorKind::$kind => $code,
+                        )*
+                        ErrorKind::Unknown(_) => "unknown",
+                        ErrorKind::Rule(code) => code,
+                    }
+                }
+
+                /// Get the name of the kind, as used in message ids.
+                fn name(&self) -> &'static str {
+                    match self {
+                        $(
+                            ErrorKind::$kind => stringify!([<$kind:snake>]),
+                        )*
+                        ErrorKind::Unknown(_) => "unknown",
+                        ErrorKind::Rule(_) => "rule",
+                    }
+                }
+            }
+        }
+    };
+}
+
+define_errors! {
+    FatalFailure => "fatal_failure",
+    Integer => "bad_integer",
+    CfgfileOpen => "config_open_failed",
+    CfgfileMalformed => "config_malformed",
+    UnknownSetting => "unknown_setting",
+    NonIntSetting => "not_integer_setting",
+    NonStrSetting => "not_string_setting",
+    MemAlloc => "out_of_memory",
+    TooSimilar => "too_similar",
+    MinDigits => "too_few_digits",
+    MinUppers => "too_few_uppercase",
+    MinLowers => "too_few_lowercase",
+    MinOthers => "too_few_others",
+    MinLength => "too_short",
+    Palindrome => "palindrome",
+    CaseChangesOnly => "case_changes_only",
+    Rotated => "rotated",
+    MinClasses => "too_few_classes",
+    MaxConsecutive => "too_many_repeated",
+    EmptyPassword => "empty_password",
+    SamePassword => "same_as_old",
+    CracklibCheck => "dictionary_word",
+    Rng => "rng_failed",
+    GenerationFailed => "generation_failed",
+    UserCheck => "contains_user_name",
+    GecosCheck => "contains_real_name",
+    MaxClassRepeat => "too_many_same_class",
+    BadWords => "forbidden_word",
+    MaxSequence => "monotonic_sequence",
+}
+
+/// Auxiliary value of an error, such as the required length or the unknown setting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Aux {
+    None,
+    Int(i64),
+    Str(String),
+    Message { id: String, text: String },
+    Similarity(SimilarityReason),
 }
 
 /// `PWQuality` Error.
-pub struct PWQError(String);
+///
+/// The error keeps the raw auxiliary value returned by libpwquality, and the message is
+/// rendered on demand into a buffer owned by the call, so errors can be formatted from
+/// any thread.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PWQError {
+    kind: ErrorKind,
+    aux: Aux,
+}
 
 impl PWQError {
     fn new_aux(error_code: i32, aux_error: Option<*mut c_void>) -> Self {
-        unsafe {
-            let s =
-                sys::pwquality_strerror(null_mut(), 0, error_code, aux_error.unwrap_or(null_mut()))
-                    .as_ref()
-                    .map(|p| CStr::from_ptr(p).to_string_lossy().to_string())
-                    .unwrap_or(format!("Unknown error: errcode={error_code}"));
+        let kind = ErrorKind::from_code(error_code);
+        let ptr = aux_error.unwrap_or(null_mut());
+
+        let aux = match &kind {
+            _ if ptr.is_null() => Aux::None,
+            ErrorKind::MinDigits
+            | ErrorKind::MinUppers
+            | ErrorKind::MinLowers
+            | ErrorKind::MinOthers
+            | ErrorKind::MinLength
+            | ErrorKind::MinClasses
+            | ErrorKind::MaxConsecutive
+            | ErrorKind::MaxClassRepeat
+            | ErrorKind::MaxSequence => Aux::Int(ptr as i64),
+            ErrorKind::CracklibCheck
+            | ErrorKind::UnknownSetting
+            | ErrorKind::Integer
+            | ErrorKind::MemAlloc
+            | ErrorKind::NonIntSetting
+            | ErrorKind::NonStrSetting => unsafe {
+                Aux::Str(CStr::from_ptr(ptr.cast()).to_string_lossy().to_string())
+            },
+            _ => Aux::None,
+        };
 
-            Self(s)
+        // the setting names are allocated for pwquality_strerror to free
+        if matches!(aux, Aux::Str(_)) && Self::frees_aux(&kind) {
+            unsafe { libc::free(ptr) };
+        }
+
+        Self { kind, aux }
+    }
+
+    /// Whether `pwquality_strerror` frees the string auxiliary value of this kind.
+    fn frees_aux(kind: &ErrorKind) -> bool {
+        matches!(
+            kind,
+            ErrorKind::MemAlloc | ErrorKind::UnknownSetting | ErrorKind::Integer
+        )
+    }
+
+    /// Render the message with `pwquality_strerror` into a buffer owned by the call.
+    fn message(&self) -> String {
+        if let Aux::Message { text, .. } = &self.aux {
+            return text.clone();
+        }
+
+        let mut buf: [c_char; sys::PWQ_MAX_ERROR_MESSAGE_LEN as usize] =
+            [0; sys::PWQ_MAX_ERROR_MESSAGE_LEN as usize];
+        let code = self.kind.code();
+
+        // pwquality_strerror may free the auxiliary string, so it gets its own copy
+        let aux_str = match &self.aux {
+            Aux::Str(s) => CString::new(s.as_str()).ok(),
+            _ => None,
+        };
+        let aux: *mut c_void = match (&self.aux, &aux_str) {
+            (Aux::Int(v), _) => *v as *mut c_void,
+            (Aux::Str(_), Some(s)) if Self::frees_aux(&self.kind) => unsafe {
+                libc::strdup(s.as_ptr()).cast()
+            },
+            (Aux::Str(_), Some(s)) => s.as_ptr() as *mut c_void,
+            _ => null_mut(),
+        };
+
+        let message = unsafe {
+            sys::pwquality_strerror(buf.as_mut_ptr(), buf.len(), code, aux)
+                .as_ref()
+                .map(|p| CStr::from_ptr(p).to_string_lossy().to_string())
+                .unwrap_or(format!("Unknown error: errcode={code}"))
+        };
+
+        // libpwquality has no auxiliary value for a malformed file, the crate gives the
+        // line it rejects, such as a bad deny pattern
+        match (&self.kind, &self.aux) {
+            (ErrorKind::CfgfileMalformed, Aux::Str(s)) => format!("{message} - {s}"),
+            _ => message,
         }
     }
 
     fn new(error_code: i32) -> Self {
         Self::new_aux(error_code, None)
     }
+
+    /// Create an error of libpwquality with a string auxiliary value found in Rust.
+    fn with_str(kind: ErrorKind, aux: &str) -> Self {
+        Self {
+            kind,
+            aux: Aux::Str(aux.to_string()),
+        }
+    }
+
+    /// Create an error of a custom rule, with the stable code of the rule and the
+    /// message in English.
+    ///
+    /// The message is translated by [`text`](Self::text) with the `rule.<code>` message
+    /// id if the catalog has it. libpwquality has no error code for custom rules, so
+    /// [`ErrorKind::code`] of the error is `PWQ_ERROR_FATAL_FAILURE`, which is what
+    /// callers that only know the libpwquality codes, such as PAM modules, get.
+    ///
+    /// It is an error if the code is the stable code of a kind of libpwquality, such as
+    /// `too_short`, or `unknown`.
+    pub fn rule(
+        code: impl Into<Cow<'static, str>>,
+        message: &str,
+    ) -> std::result::Result<Self, String> {
+        let code = code.into();
+        if code == "unknown" || code.parse::<ErrorKind>().is_ok() {
+            return Err(format!("Reserved rule code: {code:?}"));
+        }
+
+        Ok(Self {
+            aux: Aux::Message {
+                id: format!("rule.{code}"),
+                text: message.to_string(),
+            },
+            kind: ErrorKind::Rule(code),
+        })
+    }
+
+    /// Create an error explaining why the password is too similar to a previous one.
+    pub(crate) fn similar(reason: SimilarityReason) -> Self {
+        Self {
+            kind: reason.kind(),
+            aux: Aux::Similarity(reason),
+        }
+    }
+
+    /// Use another message id to translate the message of a custom rule.
+    pub fn with_message_id(mut self, message_id: &str) -> Self {
+        if let Aux::Message { id, .. } = &mut self.aux {
+            *id = message_id.to_string();
+        }
+        self
+    }
+
+    /// Get the kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind.clone()
+    }
+
+    /// Get the stable code of the error, such as `too_short`.
+    pub fn stable_code(&self) -> &str {
+        self.kind.stable_code()
+    }
+
+    /// Get the auxiliary value as a named parameter, such as `min` for the required
+    /// number of characters, `setting` for an unknown setting, `word` for the matched
+    /// forbidden word or `reason` for the similarity to a previous password.
+    pub fn param(&self) -> Option<(&'static str, String)> {
+        let name = match self.kind {
+            ErrorKind::MinDigits
+            | ErrorKind::MinUppers
+            | ErrorKind::MinLowers
+            | ErrorKind::MinOthers
+            | ErrorKind::MinLength
+            | ErrorKind::MinClasses => "min",
+            ErrorKind::MaxConsecutive | ErrorKind::MaxClassRepeat | ErrorKind::MaxSequence => "max",
+            ErrorKind::CracklibCheck | ErrorKind::CfgfileMalformed => "reason",
+            ErrorKind::BadWords => "word",
+            _ => "setting",
+        };
+
+        match &self.aux {
+            Aux::None | Aux::Message { .. } => None,
+            Aux::Int(v) => Some((name, v.to_string())),
+            Aux::Str(s) => Some((name, s.clone())),
+            Aux::Similarity(reason) => Some(("reason", reason.to_string())),
+        }
+    }
+
+    /// Get why the password is too similar to a previous one, if it is.
+    pub fn similarity_reason(&self) -> Option<&SimilarityReason> {
+        match &self.aux {
+            Aux::Similarity(reason) => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Get the integer auxiliary value, such as the required number of characters.
+    pub fn aux_int(&self) -> Option<i64> {
+        match self.aux {
+            Aux::Int(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Get the string auxiliary value, such as the unknown setting, the reason of the
+    /// dictionary check failure or the matched forbidden word.
+    pub fn aux_str(&self) -> Option<&str> {
+        match &self.aux {
+            Aux::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.stable_code())
+    }
+}
+
+impl std::str::FromStr for ErrorKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|kind| kind.stable_code() == s)
+            .cloned()
+            .ok_or_else(|| format!("unknown error code: {s}"))
+    }
 }
 
 impl std::error::Error for PWQError {}
 
 impl std::fmt::Debug for PWQError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "PWQError: {}", self.0)
+        write!(f, "PWQError: {}", self.message())
     }
 }
 
 impl std::fmt::Display for PWQError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.message())
     }
 }
 
 type Result<T> = std::result::Result<T, PWQError>;
 
-macro_rules! define_getseters {
-    (#[$doc_meta:meta] $(#[$feat_meta:meta])? $func:ident, $setting:ident) => {
-        paste! {
-            $(#[$feat_meta])?
-            #[doc = " Get"]
-            #[$doc_meta]
-            pub fn [<get_ $func>] (&self) -> i32 {
-                self.get_int_value($crate::Setting::$setting)
-            }
-
-            $(#[$feat_meta])?
-            #[doc = " Set"]
-            #[$doc_meta]
-            pub fn $func(&self, value: i32) -> &Self {
-                self.set_int_value($crate::Setting::$setting, value)
-            }
-        }
-    };
-    (#[$doc_meta:meta] $(#[$feat_meta:meta])? $func:ident, $setting:ident, bool) => {
-        paste! {
-            $(#[$feat_meta])?
-            #[doc = " Get"]
-            #[$doc_meta]
-            pub fn [<get_ $func>] (&self) -> bool {
-                self.get_int_value($crate::Setting::$setting) != 0
-            }
-
-            $(#[$feat_meta])?
-            #[doc = " Set"]
-            #[$doc_meta]
-            pub fn $func(&self, value: bool) -> &Self {
-                self.set_int_value($crate::Setting::$setting, i32::from(value))
-            }
-        }
-    };
-}
-
 /// `PWQuality` instance that holds the underlying [pwquality_settings_t](sys::pwquality_settings_t).
 pub struct PWQuality {
     pwq: *mut sys::pwquality_settings_t,
+    passwd: RefCell<Option<passwd::Passwd>>,
+    rules: RefCell<Vec<Rc<dyn Rule>>>,
+    #[cfg(feature = "regex")]
+    deny_patterns: RefCell<Vec<DenyPattern>>,
 }
 
 impl PWQuality {
@@ -167,7 +803,13 @@ impl PWQuality {
 
             ptr.as_ref()
                 .ok_or(PWQError::new(sys::PWQ_ERROR_MEM_ALLOC))
-                .map(|_| Self { pwq: ptr })
+                .map(|_| Self {
+                    pwq: ptr,
+                    passwd: RefCell::new(None),
+                    rules: RefCell::new(vec![]),
+                    #[cfg(feature = "regex")]
+                    deny_patterns: RefCell::new(vec![]),
+                })
         }
     }
 
@@ -183,9 +825,21 @@ impl PWQuality {
 
     /// Parse the configuration file.
     fn read_optional_config<P: AsRef<Path>>(&self, path: Option<P>) -> Result<&Self> {
+        let path = path.as_ref().map(AsRef::as_ref);
+        let ret = self.read_libpwquality_config(path);
+
+        #[cfg(feature = "regex")]
+        return ret.or_else(|e| self.read_deny_pattern_config(path, e));
+
+        #[cfg(not(feature = "regex"))]
+        ret
+    }
+
+    /// Parse the configuration file with libpwquality.
+    fn read_libpwquality_config(&self, path: Option<&Path>) -> Result<&Self> {
         let mut aux_error = null_mut();
         let c_path = path
-            .map(|p| CString::new(p.as_ref().to_string_lossy().to_string()))
+            .map(|p| CString::new(p.to_string_lossy().to_string()))
             .transpose()
             .unwrap();
 
@@ -204,6 +858,70 @@ impl PWQuality {
         }
     }
 
+    /// Set a setting from a `name=value` string as found in the configuration file.
+    ///
+    /// Settings without value such as `enforce_for_root` are given as the bare key.
+    pub fn set_option(&self, option: &str) -> Result<&Self> {
+        #[cfg(feature = "regex")]
+        if let Some(ret) = self.set_deny_pattern_option(option) {
+            return ret;
+        }
+
+        let c_option = CString::new(option).unwrap();
+        let ret = unsafe { sys::pwquality_set_option(self.pwq, c_option.as_ptr()) };
+
+        if ret == 0 {
+            Ok(self)
+        } else {
+            Err(PWQError::new(ret))
+        }
+    }
+
+    /// Get the value of a setting by its configuration file key, formatted as in the
+    /// configuration file.
+    ///
+    /// Boolean settings are returned as `0` or `1`, and the bad words are separated by
+    /// spaces.
+    pub fn get_option(&self, key: &str) -> Result<String> {
+        let setting = key
+            .parse::<Setting>()
+            .map_err(|_| PWQError::new(sys::PWQ_ERROR_UNKNOWN_SETTING))?;
+
+        if setting.is_str() {
+            return self.get_str_value(setting);
+        }
+
+        self.try_get_int_value(setting)
+            .map(|value| value.to_string())
+    }
+
+    /// Copy the libpwquality settings into new settings, leaving out the custom checks.
+    ///
+    /// The settings that libpwquality can't read, such as `usersubstr` before 1.4.5, keep
+    /// their default values.
+    fn copy_settings(&self) -> Result<Self> {
+        let copy = Self::new()?;
+
+        for &setting in Setting::ALL {
+            if !setting.is_str() {
+                if let Ok(value) = self.try_get_int_value(setting) {
+                    copy.set_int_value(setting, value);
+                }
+                continue;
+            }
+
+            // unset strings read as empty
+            match self.get_str_value(setting) {
+                Ok(value) if !value.is_empty() => {
+                    copy.set_str_value(setting, &value)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(copy)
+    }
+
     /// Set value of an integer setting.
     fn set_int_value(&self, setting: Setting, value: i32) -> &Self {
         let ret = unsafe { sys::pwquality_set_int_value(self.pwq, setting as c_int, value) };
@@ -215,12 +933,23 @@ impl PWQuality {
 
     /// Get value of an integer setting.
     fn get_int_value(&self, setting: Setting) -> i32 {
+        let value = self.try_get_int_value(setting);
+
+        debug_assert!(value.is_ok());
+
+        value.unwrap_or_default()
+    }
+
+    /// Get value of an integer setting, which fails if libpwquality can't read it.
+    fn try_get_int_value(&self, setting: Setting) -> Result<i32> {
         let mut value: i32 = 0;
         let ret = unsafe { sys::pwquality_get_int_value(self.pwq, setting as c_int, &mut value) };
 
-        debug_assert!(ret == 0);
-
-        value
+        if ret == 0 {
+            Ok(value)
+        } else {
+            Err(PWQError::new(ret))
+        }
     }
 
     /// Set value of a string setting.
@@ -256,17 +985,24 @@ impl PWQuality {
 
     /// Generate a random password of entropy_bits entropy and check it according to the settings.
     pub fn generate(&self, bits: i32) -> Result<String> {
+        self.generate_bytes(bits)
+            .map(|v| String::from_utf8_lossy(&v).to_string())
+    }
+
+    /// Generate a random password of entropy_bits entropy and return the raw bytes
+    /// produced by the C library.
+    pub fn generate_bytes(&self, bits: i32) -> Result<Vec<u8>> {
         let mut ptr: *mut c_char = null_mut();
         unsafe {
             let ret = sys::pwquality_generate(self.pwq, bits, &mut ptr);
 
             ptr.as_ref().ok_or(PWQError::new(ret)).map(|p| {
-                let s = CStr::from_ptr(p).to_string_lossy().to_string();
+                let v = CStr::from_ptr(p).to_bytes().to_vec();
 
                 // free the memory allocated in the C library
                 libc::free(ptr.cast());
 
-                s
+                v
             })
         }
     }
@@ -277,6 +1013,40 @@ impl PWQuality {
         password: &str,
         old_password: Option<&str>,
         user: Option<&str>,
+    ) -> Result<i32> {
+        self.check_bytes(
+            password.as_bytes(),
+            old_password.map(str::as_bytes),
+            user.map(str::as_bytes),
+        )
+    }
+
+    /// Check the password according to the settings, where the password, the old password
+    /// and the user name are OS strings.
+    pub fn check_os_str(
+        &self,
+        password: &OsStr,
+        old_password: Option<&OsStr>,
+        user: Option<&OsStr>,
+    ) -> Result<i32> {
+        self.check_bytes(
+            password.as_bytes(),
+            old_password.map(OsStr::as_bytes),
+            user.map(OsStr::as_bytes),
+        )
+    }
+
+    /// Check the password according to the settings, where the password, the old password
+    /// and the user name are raw bytes in any encoding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the arguments contains an interior nul byte.
+    pub fn check_bytes(
+        &self,
+        password: &[u8],
+        old_password: Option<&[u8]>,
+        user: Option<&[u8]>,
     ) -> Result<i32> {
         let c_password = CString::new(password).unwrap();
         let mut aux_error = null_mut();
@@ -284,9 +1054,22 @@ impl PWQuality {
         let c_old_password = old_password.map(CString::new).transpose().unwrap();
         let c_user = user.map(CString::new).transpose().unwrap();
 
+        // libpwquality looks up the GECOS field of the user in the passwd database of the
+        // system, so the check is done here, on a copy of the settings without it, when an
+        // alternate passwd database is used
+        let gecos_user = user.filter(|_| self.has_alternate_passwd() && self.get_gecos_check());
+        let copy = match gecos_user {
+            Some(_) => Some(self.copy_settings()?),
+            None => None,
+        };
+        let pwq = match &copy {
+            Some(copy) => copy.gecos_check(false).pwq,
+            None => self.pwq,
+        };
+
         let ret = unsafe {
             sys::pwquality_check(
-                self.pwq,
+                pwq,
                 c_password.as_ptr(),
                 c_old_password
                     .as_ref()
@@ -299,124 +1082,14 @@ impl PWQuality {
 
         if ret < 0 {
             Err(PWQError::new_aux(ret, Some(aux_error)))
+        } else if gecos_user.is_some_and(|user| self.contains_gecos(password, user)) {
+            Err(PWQError::new(sys::PWQ_ERROR_GECOS_CHECK))
         } else {
-            Ok(ret)
-        }
-    }
-
-    define_getseters! {
-        #[doc = " the minimum number of characters in the new password that must not be present in the old password."]
-        min_diff,
-        DiffOk
-    }
-
-    define_getseters! {
-        #[doc = " the minimum acceptable size for the new password."]
-        min_length,
-        MinLength
-    }
-
-    define_getseters! {
-        #[doc = " the maximum credit for having digits in the new password."]
-        digit_credit,
-        DigCredit
-    }
+            #[cfg(feature = "regex")]
+            self.check_deny_patterns(password)?;
 
-    define_getseters! {
-        #[doc = " the maximum credit for having uppercase characters in the new password."]
-        uppercase_credit,
-        UpCredit
-    }
-
-    define_getseters! {
-        #[doc = " the maximum credit for having lowercase characters in the new password."]
-        lowercase_credit,
-        LowCredit
-    }
-
-    define_getseters! {
-        #[doc = " the maximum credit for having other characters in the new password."]
-        other_credit,
-        OthCredit
-    }
-
-    define_getseters! {
-        #[doc = " the minimum number of required classes of characters for the new password."]
-        min_class,
-        MinClass
-    }
-
-    define_getseters! {
-        #[doc = " the maximum number of allowed same consecutive characters in the new password."]
-        max_repeat,
-        MaxRepeat
-    }
-
-    define_getseters! {
-        #[doc = " the maximum number of allowed consecutive characters of the same class in the new password."]
-        max_class_repeat,
-        MaxClassRepeat
-    }
-
-    define_getseters! {
-        #[doc = " the maximum length of monotonic character sequences in the new password."]
-        #[cfg(any(feature = "v1_2", feature = "vendored", feature = "vendored-cracklib"))]
-        max_sequence,
-        MaxSequence
-    }
-
-    define_getseters! {
-        #[doc = " whether to perform the passwd GECOS field check."]
-        gecos_check,
-        GecosCheck,
-        bool
-    }
-
-    define_getseters! {
-        #[doc = " whether to perform the dictionary check."]
-        #[cfg(any(feature = "v1_3", feature = "vendored", feature = "vendored-cracklib"))]
-        dict_check,
-        DictCheck,
-        bool
-    }
-
-    define_getseters! {
-        #[doc = " whether to perform the user name check."]
-        #[cfg(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib"))]
-        user_check,
-        UserCheck,
-        bool
-    }
-
-    define_getseters! {
-        #[doc = " whether the check is enforced."]
-        #[cfg(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib"))]
-        enforcing,
-        Enforcing,
-        bool
-    }
-
-    define_getseters! {
-        #[doc = " maximum retries for the password change should be allowed."]
-        #[cfg(any(feature = "v1_4_1", feature = "vendored", feature = "vendored-cracklib"))]
-        retry_times,
-        RetryTimes
-    }
-
-    define_getseters! {
-        #[doc = " whether the check is enforced for root."]
-        #[cfg(any(feature = "v1_4_1", feature = "vendored", feature = "vendored-cracklib"))]
-        enforce_for_root,
-        EnforceRoot,
-        bool
-    }
-
-    define_getseters! {
-        #[doc = " whether to check local users only."]
-        #[cfg(any(feature = "v1_4_1", feature = "vendored", feature = "vendored-cracklib"))]
-        local_users_only,
-        LocalUsers,
-        bool
+            self.check_rules(password, old_password, user, ret)
+        }
     }
 
     #[cfg(any(
@@ -439,6 +1112,26 @@ impl PWQuality {
         self.set_int_value(crate::Setting::UserSubstr, value)
     }
 
+    /// Get the shortest length a password can have according to the settings.
+    ///
+    /// Each character of a class with a positive credit counts as an additional
+    /// character, up to the credit, so the minimum length can be lower than
+    /// [`get_min_length`](Self::get_min_length).
+    pub fn effective_min_length(&self) -> i32 {
+        let credits = [
+            self.get_digit_credit(),
+            self.get_uppercase_credit(),
+            self.get_lowercase_credit(),
+            self.get_other_credit(),
+        ];
+        let bonus: i32 = credits.iter().filter(|&&c| c > 0).sum();
+        let required: i32 = credits.iter().filter(|&&c| c < 0).map(|c| -c).sum();
+        let min_length = self.get_min_length();
+
+        // A password of n characters earns at most n credits.
+        (min_length - bonus).max((min_length + 1) / 2).max(required)
+    }
+
     /// Set the list of words more than 3 characters long that are forbidden.
     pub fn bad_words<W>(&self, words: W) -> Result<&Self>
     where
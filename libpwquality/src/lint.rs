@@ -0,0 +1,390 @@
+use crate::{sys, ErrorKind, PWQuality, Result, SettingInfo};
+
+/// Severity of a lint diagnostic.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The setting has no effect or is explained for information.
+    Info,
+    /// The setting is accepted but probably not what was intended.
+    Warning,
+    /// The setting is rejected or makes passwords impossible to use or generate.
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+/// A problem found by the linter.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    /// Severity of the problem.
+    pub severity: Severity,
+    /// Line of the configuration file, starting from 1.
+    pub line: Option<usize>,
+    /// Configuration key the problem is about.
+    pub key: Option<String>,
+    /// Human-readable description of the problem.
+    pub message: String,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "{line}: ")?;
+        }
+
+        write!(f, "{}: ", self.severity)?;
+
+        if let Some(key) = &self.key {
+            write!(f, "{key}: ")?;
+        }
+
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Result of linting a configuration.
+#[derive(Clone, Debug)]
+pub struct LintReport {
+    /// Problems found in the configuration.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The shortest length a password can have according to the configuration.
+    pub effective_min_length: i32,
+}
+
+impl LintReport {
+    /// Whether any diagnostic is an error.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+}
+
+/// Collects diagnostics.
+struct Linter(Vec<Diagnostic>);
+
+impl Linter {
+    fn push(
+        &mut self,
+        severity: Severity,
+        line: Option<usize>,
+        key: Option<&str>,
+        message: String,
+    ) {
+        self.0.push(Diagnostic {
+            severity,
+            line,
+            key: key.map(String::from),
+            message,
+        });
+    }
+
+    fn setting(&mut self, severity: Severity, key: &str, message: String) {
+        self.push(severity, None, Some(key), message);
+    }
+
+    fn line(&mut self, severity: Severity, line: usize, key: &str, message: String) {
+        self.push(severity, Some(line), Some(key), message);
+    }
+}
+
+/// Lint the content of a `pwquality.conf` file.
+///
+/// Besides problems of the individual lines, the settings resulting from applying the file
+/// on top of the library defaults are checked with [`PWQuality::lint`].
+pub fn lint_config(text: &str) -> Result<LintReport> {
+    let pwq = PWQuality::new()?;
+    let mut linter = Linter(Vec::new());
+    let mut seen: Vec<(&str, usize)> = Vec::new();
+
+    for (n, line) in text.lines().enumerate() {
+        let n = n + 1;
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (name, value) = match line.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim())),
+            None => (line, None),
+        };
+
+        #[cfg(feature = "regex")]
+        if name == crate::pattern::DENY_PATTERN_KEY {
+            if let Err(e) = value.unwrap_or_default().parse::<crate::DenyPattern>() {
+                linter.line(Severity::Error, n, name, e);
+            }
+            continue;
+        }
+
+        let Some(info) = SettingInfo::find(name) else {
+            linter.line(Severity::Error, n, name, "unknown setting".to_string());
+            continue;
+        };
+
+        if !info.supported {
+            linter.line(
+                Severity::Error,
+                n,
+                name,
+                format!(
+                    "the setting requires libpwquality {} or later, which is not linked",
+                    info.since
+                ),
+            );
+            continue;
+        }
+
+        if let Some((_, first)) = seen.iter().find(|(s, _)| *s == name) {
+            linter.line(
+                Severity::Warning,
+                n,
+                name,
+                format!("the setting is already set at line {first}, the last value is used"),
+            );
+        } else {
+            seen.push((name, n));
+        }
+
+        if info.bare && value.is_some_and(|value| !value.is_empty()) {
+            linter.line(
+                Severity::Warning,
+                n,
+                name,
+                "the setting takes no value, the value is ignored".to_string(),
+            );
+        }
+
+        // libpwquality accepts some values that the registry rejects, and adjusts them
+        match (info.validate(value), pwq.set_option(line)) {
+            (Ok(()), Ok(_)) => {}
+            (Err(e), Ok(_)) => linter.line(
+                Severity::Warning,
+                n,
+                name,
+                format!("{e}, libpwquality adjusts the value"),
+            ),
+            (Err(e), Err(_)) => linter.line(Severity::Error, n, name, e),
+            (Ok(()), Err(e)) => linter.line(Severity::Error, n, name, e.to_string()),
+        }
+    }
+
+    let mut report = pwq.lint();
+    linter.0.append(&mut report.diagnostics);
+    report.diagnostics = linter.0;
+
+    Ok(report)
+}
+
+impl PWQuality {
+    /// Check the current settings for contradictory, ineffective or unsatisfiable combinations.
+    pub fn lint(&self) -> LintReport {
+        let mut linter = Linter(Vec::new());
+        let min_length = self.get_min_length();
+        let effective_min_length = self.effective_min_length();
+        let min_class = self.get_min_class();
+        let credits = [
+            ("dcredit", self.get_digit_credit()),
+            ("ucredit", self.get_uppercase_credit()),
+            ("lcredit", self.get_lowercase_credit()),
+            ("ocredit", self.get_other_credit()),
+        ];
+        let bonus: i32 = credits.iter().map(|(_, c)| c).filter(|&&c| c > 0).sum();
+        let required: i32 = credits
+            .iter()
+            .map(|(_, c)| c)
+            .filter(|&&c| c < 0)
+            .map(|c| -c)
+            .sum();
+        let required_classes = credits.iter().filter(|(_, c)| *c < 0).count() as i32;
+
+        if min_class >= 4 && credits.iter().all(|(_, c)| *c > 0) {
+            linter.setting(
+                Severity::Warning,
+                "minclass",
+                format!(
+                    "all four classes are required and every credit is positive, \
+                     so every password earns credits and minlen {min_length} is effectively {}",
+                    min_length - 4
+                ),
+            );
+        } else if bonus > 0 && effective_min_length < min_length {
+            linter.setting(
+                Severity::Info,
+                "minlen",
+                format!(
+                    "positive credits allow passwords of {effective_min_length} characters \
+                     although minlen is {min_length}"
+                ),
+            );
+        }
+
+        if min_class > 0 && required_classes >= min_class {
+            linter.setting(
+                Severity::Info,
+                "minclass",
+                format!(
+                    "negative credits already require {required_classes} classes, \
+                     minclass {min_class} has no effect"
+                ),
+            );
+        }
+
+        if required > min_length {
+            linter.setting(
+                Severity::Info,
+                "minlen",
+                format!(
+                    "negative credits require {required} characters, \
+                     more than minlen {min_length}"
+                ),
+            );
+        }
+
+        let min_diff = self.get_min_diff();
+        if min_diff > effective_min_length {
+            linter.setting(
+                Severity::Warning,
+                "difok",
+                format!(
+                    "difok {min_diff} is greater than the minimum length {effective_min_length}, \
+                     short passwords are always too similar to the old one"
+                ),
+            );
+        }
+
+        if self.get_max_class_repeat() == 1 {
+            linter.setting(
+                Severity::Warning,
+                "maxclassrepeat",
+                "no two consecutive characters may be of the same class, \
+                 most passwords are rejected and generation is likely to fail"
+                    .to_string(),
+            );
+        }
+
+        #[cfg(any(feature = "v1_2", feature = "vendored", feature = "vendored-cracklib"))]
+        if (1..=2).contains(&self.get_max_sequence()) {
+            linter.setting(
+                Severity::Warning,
+                "maxsequence",
+                format!(
+                    "sequences longer than {} characters such as \"abc\" are rejected, \
+                     generation is likely to fail",
+                    self.get_max_sequence()
+                ),
+            );
+        }
+
+        self.lint_generation(&mut linter);
+
+        if let Ok(words) = self.get_bad_words() {
+            for word in words.iter().filter(|w| w.chars().count() <= 3) {
+                linter.setting(
+                    Severity::Warning,
+                    "badwords",
+                    format!("{word:?} is not longer than 3 characters and is ignored"),
+                );
+            }
+        }
+
+        #[cfg(any(feature = "v1_3", feature = "vendored", feature = "vendored-cracklib"))]
+        if !self.get_dict_check() && self.get_dict_path().is_ok_and(|p| !p.is_empty()) {
+            linter.setting(
+                Severity::Warning,
+                "dictpath",
+                "dictcheck is disabled, the dictionary is not used".to_string(),
+            );
+        }
+
+        #[cfg(any(
+            feature = "v1_4_5",
+            feature = "vendored",
+            feature = "vendored-cracklib"
+        ))]
+        if !self.get_user_check() && self.get_user_substr() > 0 {
+            linter.setting(
+                Severity::Warning,
+                "usersubstr",
+                "usercheck is disabled, user name substrings are not checked".to_string(),
+            );
+        }
+
+        #[cfg(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib"))]
+        if !self.get_enforcing() {
+            linter.setting(
+                Severity::Info,
+                "enforcing",
+                "rejected passwords are only warned about".to_string(),
+            );
+        }
+
+        LintReport {
+            diagnostics: linter.0,
+            effective_min_length,
+        }
+    }
+
+    /// Check that passwords can be generated, and with how many bits of entropy.
+    fn lint_generation(&self, linter: &mut Linter) {
+        let min_bits = sys::PWQ_MIN_ENTROPY_BITS as i32;
+        let max_bits = sys::PWQ_MAX_ENTROPY_BITS as i32;
+
+        for bits in (min_bits..=max_bits).step_by(8) {
+            match self.generate(bits) {
+                Ok(_) if bits > min_bits => {
+                    return linter.setting(
+                        Severity::Info,
+                        "minlen",
+                        format!("password generation needs at least {bits} bits of entropy"),
+                    )
+                }
+                Ok(_) => return,
+                Err(e) if e.kind() == ErrorKind::GenerationFailed => {}
+                Err(e) => {
+                    return linter.push(
+                        Severity::Error,
+                        None,
+                        None,
+                        format!("password generation fails: {e}"),
+                    )
+                }
+            }
+        }
+
+        // libpwquality reports generated passwords failing the dictionary check as a
+        // generation failure too, such as when the dictionary can't be loaded
+        #[cfg(any(feature = "v1_3", feature = "vendored", feature = "vendored-cracklib"))]
+        if self.get_dict_check()
+            && self
+                .copy_settings()
+                .is_ok_and(|copy| copy.dict_check(false).generate(max_bits).is_ok())
+        {
+            return linter.setting(
+                Severity::Error,
+                "dictpath",
+                "generated passwords fail the dictionary check, \
+                 check that the cracklib dictionary can be loaded"
+                    .to_string(),
+            );
+        }
+
+        linter.setting(
+            Severity::Error,
+            "minlen",
+            "password generation fails with these settings, \
+             check minlen, maxrepeat, maxclassrepeat and maxsequence"
+                .to_string(),
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{ConfigFile, PWQuality, Result, SettingInfo};
+
+/// Settings whose `pam_cracklib` default differs from the libpwquality one.
+const CRACKLIB_DEFAULTS: &[(&str, &str)] = &[
+    ("difok", "5"),
+    ("minlen", "9"),
+    ("dcredit", "1"),
+    ("ucredit", "1"),
+    ("lcredit", "1"),
+    ("ocredit", "1"),
+    ("usercheck", "0"),
+];
+
+/// Location of a module line in a PAM stack file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    /// Name of the PAM stack file.
+    pub file: String,
+    /// Line number, starting from 1.
+    pub line: usize,
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+/// A module argument that couldn't be moved to `pwquality.conf`.
+#[derive(Clone, Debug)]
+pub struct Untranslated {
+    /// Where the argument was found.
+    pub location: Location,
+    /// The argument.
+    pub argument: String,
+    /// Why the argument wasn't translated.
+    pub reason: String,
+}
+
+/// A module line of a PAM stack file and its replacement.
+#[derive(Clone, Debug)]
+pub struct PamLineChange {
+    /// Where the line was found.
+    pub location: Location,
+    /// The original line.
+    pub old: String,
+    /// The line using `pam_pwquality.so` with the module-only arguments.
+    pub new: String,
+}
+
+/// Result of migrating `pam_cracklib`/`pam_pwquality` arguments to `pwquality.conf`.
+#[derive(Clone, Debug, Default)]
+pub struct Migration {
+    /// The equivalent `pwquality.conf`.
+    pub config: ConfigFile,
+    /// Arguments that couldn't be translated.
+    pub untranslated: Vec<Untranslated>,
+    /// Changes of the PAM stack files.
+    pub changes: Vec<PamLineChange>,
+}
+
+impl Migration {
+    /// Format the PAM stack changes and the untranslated arguments as a diff.
+    pub fn diff(&self) -> String {
+        let mut s = String::new();
+
+        for change in &self.changes {
+            s += &format!(
+                "@@ {} @@\n-{}\n+{}\n",
+                change.location, change.old, change.new
+            );
+        }
+
+        for u in &self.untranslated {
+            s += &format!("# {}: {}: {}\n", u.location, u.argument, u.reason);
+        }
+
+        s
+    }
+}
+
+/// A module line of a PAM stack file, with continuations joined and comments stripped.
+struct ModuleLine {
+    line: usize,
+    text: String,
+}
+
+/// Split a line into tokens, `[...]` is a single token.
+fn tokenize(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = line.trim_start();
+
+    while !rest.is_empty() {
+        let end = if rest.starts_with('[') {
+            rest.find(']').map_or(rest.len(), |i| i + 1)
+        } else {
+            rest.find(char::is_whitespace).unwrap_or(rest.len())
+        };
+
+        tokens.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+
+    tokens
+}
+
+/// Get the `password` module lines, joining continued lines and stripping comments.
+fn module_lines(text: &str) -> Vec<ModuleLine> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut logical = String::new();
+
+    for (n, line) in text.lines().enumerate() {
+        if logical.is_empty() {
+            start = n + 1;
+        }
+
+        match line.strip_suffix('\\') {
+            Some(line) => {
+                logical.push_str(line);
+                logical.push(' ');
+                continue;
+            }
+            None => logical.push_str(line),
+        }
+
+        let content = logical
+            .split('#')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        logical.clear();
+
+        if matches!(tokenize(&content).first(), Some(&"password" | &"-password")) {
+            lines.push(ModuleLine {
+                line: start,
+                text: content,
+            });
+        }
+    }
+
+    lines
+}
+
+struct Migrator {
+    pwq: PWQuality,
+    migration: Migration,
+    sources: Vec<(String, String, Location)>,
+}
+
+impl Migrator {
+    fn untranslated(&mut self, location: &Location, argument: &str, reason: String) {
+        self.migration.untranslated.push(Untranslated {
+            location: location.clone(),
+            argument: argument.to_string(),
+            reason,
+        });
+    }
+
+    /// Add a setting to the configuration, reporting conflicts between stack files.
+    fn set(&mut self, location: &Location, key: &str, value: Option<&str>, argument: &str) {
+        let option = match value {
+            Some(value) => format!("{key}={value}"),
+            None => key.to_string(),
+        };
+
+        // libpwquality clamps some values that the configuration file can't hold
+        if let Some(Err(e)) = SettingInfo::find(key).map(|info| info.validate(value)) {
+            self.untranslated(location, argument, e);
+            return;
+        }
+        if let Err(e) = self.pwq.set_option(&option) {
+            self.untranslated(location, argument, e.to_string());
+            return;
+        }
+
+        let previous = self
+            .sources
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(_, v, l)| (v.clone(), l.clone()));
+
+        match previous {
+            Some((v, l)) if v != option => self.untranslated(
+                location,
+                argument,
+                format!("conflicts with {v} from {l}, which is kept"),
+            ),
+            Some(_) => {}
+            None => match self.migration.config.set(key, value) {
+                Ok(_) => self
+                    .sources
+                    .push((key.to_string(), option, location.clone())),
+                Err(e) => self.untranslated(location, argument, e.to_string()),
+            },
+        }
+    }
+
+    fn module(&mut self, location: Location, line: &ModuleLine, tokens: &[&str]) {
+        let mut keep = Vec::new();
+
+        for arg in &tokens[3..] {
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (*arg, None),
+            };
+
+            match (name, value) {
+                // read by pam_get_authtok(3) from the module arguments
+                ("try_first_pass" | "use_first_pass", None) => keep.push(*arg),
+                ("debug" | "use_authtok" | "authtok_type" | "type", _) => keep.push(*arg),
+                ("retry", Some(retry)) if retry.parse::<i32>().is_ok() => {
+                    self.set(&location, "retry", value, arg);
+                    keep.push(*arg);
+                }
+                ("enforce_for_root" | "local_users_only", None) => {
+                    self.set(&location, name, None, arg);
+                    keep.push(*arg);
+                }
+                ("reject_username", None) => self.set(&location, "usercheck", Some("1"), arg),
+                ("gecoscheck", None) => self.set(&location, "gecoscheck", Some("1"), arg),
+                ("difignore", _) => self.untranslated(
+                    &location,
+                    arg,
+                    "the setting is obsolete and has no effect".to_string(),
+                ),
+                (name, Some(value)) => self.set(&location, name, Some(value), arg),
+                (_, None) => {
+                    self.untranslated(&location, arg, format!("unknown argument of {}", tokens[2]))
+                }
+            }
+        }
+
+        if tokens[2].ends_with("pam_cracklib.so") {
+            for (key, value) in CRACKLIB_DEFAULTS {
+                let given = tokens[3..].iter().any(|arg| {
+                    arg.split('=').next() == Some(key)
+                        || (*key == "usercheck" && *arg == "reject_username")
+                });
+
+                if !given {
+                    let argument = format!("{key}={value} (pam_cracklib default)");
+                    self.set(&location, key, Some(value), &argument);
+                }
+            }
+        }
+
+        let module = tokens[2].replace("pam_cracklib.so", "pam_pwquality.so");
+        let new = tokens[..2]
+            .iter()
+            .copied()
+            .chain(std::iter::once(module.as_str()))
+            .chain(keep)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        self.migration.changes.push(PamLineChange {
+            location,
+            old: line.text.clone(),
+            new,
+        });
+    }
+}
+
+/// Migrate the `pam_cracklib` and `pam_pwquality` arguments of PAM stack files to
+/// an equivalent `pwquality.conf`.
+///
+/// The files are given as `(name, content)` pairs. The `pam_cracklib` defaults that
+/// differ from the libpwquality ones are written explicitly, and arguments rejected by
+/// the linked libpwquality are reported as untranslated.
+pub fn migrate_pam<N, T>(files: impl IntoIterator<Item = (N, T)>) -> Result<Migration>
+where
+    N: AsRef<str>,
+    T: AsRef<str>,
+{
+    let mut migrator = Migrator {
+        pwq: PWQuality::new()?,
+        migration: Migration::default(),
+        sources: Vec::new(),
+    };
+
+    for (name, text) in files {
+        for line in module_lines(text.as_ref()) {
+            let tokens = tokenize(&line.text);
+            let Some(module) = tokens.get(2) else {
+                continue;
+            };
+
+            if module.ends_with("pam_cracklib.so") || module.ends_with("pam_pwquality.so") {
+                let location = Location {
+                    file: name.as_ref().to_string(),
+                    line: line.line,
+                };
+                migrator.module(location, &line, &tokens);
+            }
+        }
+    }
+
+    Ok(migrator.migration)
+}
@@ -0,0 +1,169 @@
+use crate::context::contains_word;
+use crate::PWQuality;
+use std::path::{Path, PathBuf};
+
+/// Path of the local passwd file.
+pub const PASSWD_FILE: &str = "/etc/passwd";
+
+/// An entry of a passwd file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PasswdEntry {
+    /// User name.
+    pub name: String,
+    /// User id.
+    pub uid: u32,
+    /// Group id.
+    pub gid: u32,
+    /// GECOS field, usually the real name of the user followed by other information
+    /// separated by commas.
+    pub gecos: String,
+    /// Home directory.
+    pub home: String,
+    /// Login shell.
+    pub shell: String,
+}
+
+impl PasswdEntry {
+    /// Create an entry with only a user name and a GECOS field.
+    pub fn new(name: &str, gecos: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            gecos: gecos.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Parse the entries of a passwd file.
+    ///
+    /// Malformed lines, comments and NIS compat entries starting with `+` or `-` are
+    /// skipped, as they are by the files backend of NSS.
+    pub fn parse_file(text: &str) -> Vec<Self> {
+        text.lines()
+            .filter(|line| !line.starts_with(['#', '+', '-']))
+            .filter_map(|line| line.parse().ok())
+            .collect()
+    }
+}
+
+impl std::fmt::Display for PasswdEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:x:{}:{}:{}:{}:{}",
+            self.name, self.uid, self.gid, self.gecos, self.home, self.shell
+        )
+    }
+}
+
+impl std::str::FromStr for PasswdEntry {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let fields = s.split(':').collect::<Vec<_>>();
+        let [name, _, uid, gid, gecos, home, shell] = fields[..] else {
+            return Err(format!("Malformed passwd entry: {s}"));
+        };
+        let id = |v: &str| {
+            v.parse::<u32>()
+                .map_err(|e| format!("Bad id {v} in passwd entry: {e}"))
+        };
+
+        if name.is_empty() {
+            return Err(format!("Missing user name in passwd entry: {s}"));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            uid: id(uid)?,
+            gid: id(gid)?,
+            gecos: gecos.to_string(),
+            home: home.to_string(),
+            shell: shell.to_string(),
+        })
+    }
+}
+
+/// Source of the passwd entries used instead of the passwd database of the system.
+#[derive(Clone, Debug)]
+pub(crate) enum Passwd {
+    File(PathBuf),
+    Entries(Vec<PasswdEntry>),
+}
+
+impl Passwd {
+    fn find(&self, user: &[u8]) -> Option<PasswdEntry> {
+        let is_user = |entry: &PasswdEntry| entry.name.as_bytes() == user;
+
+        match self {
+            Passwd::File(path) => std::fs::read_to_string(path)
+                .ok()
+                .and_then(|text| PasswdEntry::parse_file(&text).into_iter().find(is_user)),
+            Passwd::Entries(entries) => entries.iter().find(|entry| is_user(entry)).cloned(),
+        }
+    }
+}
+
+impl PWQuality {
+    /// Look up users in the passwd file instead of the passwd database of the system.
+    ///
+    /// The file is read when a user is looked up, by the GECOS check and by
+    /// [`is_local_user`](Self::is_local_user). This is meant for checking passwords of
+    /// another system, such as an image being built.
+    pub fn passwd_file(&self, path: impl AsRef<Path>) -> &Self {
+        self.passwd
+            .replace(Some(Passwd::File(path.as_ref().to_path_buf())));
+        self
+    }
+
+    /// Look up users in the given entries instead of the passwd database of the system.
+    pub fn passwd_entries<E>(&self, entries: E) -> &Self
+    where
+        E: IntoIterator<Item = PasswdEntry>,
+    {
+        self.passwd
+            .replace(Some(Passwd::Entries(entries.into_iter().collect())));
+        self
+    }
+
+    /// Look up users in the passwd database of the system again.
+    pub fn system_passwd(&self) -> &Self {
+        self.passwd.replace(None);
+        self
+    }
+
+    /// Whether the user is listed in the local passwd file, as checked for
+    /// `local_users_only`.
+    ///
+    /// The alternate passwd file or entries are used if set, [`PASSWD_FILE`] otherwise.
+    pub fn is_local_user(&self, user: impl AsRef<[u8]>) -> bool {
+        let user = user.as_ref();
+
+        match &*self.passwd.borrow() {
+            Some(passwd) => passwd.find(user).is_some(),
+            None => std::fs::read(PASSWD_FILE).is_ok_and(|passwd| {
+                passwd
+                    .split(|&b| b == b'\n')
+                    .any(|line| line.strip_prefix(user).is_some_and(|s| s.starts_with(b":")))
+            }),
+        }
+    }
+
+    /// Whether an alternate passwd database is used for the GECOS check.
+    pub(crate) fn has_alternate_passwd(&self) -> bool {
+        self.passwd.borrow().is_some()
+    }
+
+    /// Whether the password contains a word of the GECOS field of the user in the
+    /// alternate passwd database.
+    pub(crate) fn contains_gecos(&self, password: &[u8], user: &[u8]) -> bool {
+        let Some(entry) = self.passwd.borrow().as_ref().and_then(|p| p.find(user)) else {
+            return false;
+        };
+        let lower = password.to_ascii_lowercase();
+
+        entry
+            .gecos
+            .split([' ', ','])
+            .any(|word| contains_word(&lower, word))
+    }
+}
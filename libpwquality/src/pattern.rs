@@ -0,0 +1,260 @@
+use crate::{sys, ErrorKind, PWQError, PWQuality, Result, DROP_IN_DIR};
+use regex::bytes::Regex;
+use std::path::{Path, PathBuf};
+
+/// Configuration key of the deny patterns.
+pub(crate) const DENY_PATTERN_KEY: &str = "denypattern";
+
+/// Stable code of the errors of the deny patterns.
+const CODE: &str = "denied_pattern";
+
+/// Path of the default configuration file read by libpwquality.
+const DEFAULT_CONFIG: &str = "/etc/security/pwquality.conf";
+
+/// A regular expression that passwords must not match.
+///
+/// In the configuration file, each `denypattern` line adds a pattern, optionally
+/// preceded by the message id of the error in braces, which can't start a regular
+/// expression:
+///
+/// ```text
+/// denypattern = ^Summer20\d\d
+/// denypattern = {policy.company_name} (?i)acme\d+
+/// ```
+///
+/// As libpwquality ends the lines at `#`, the patterns of the configuration file can't
+/// contain `#`, use `\x23` instead.
+#[derive(Clone, Debug)]
+pub struct DenyPattern {
+    regex: Regex,
+    message_id: Option<String>,
+}
+
+impl DenyPattern {
+    /// Compile the regular expression.
+    pub fn new(pattern: &str) -> std::result::Result<Self, String> {
+        Regex::new(pattern)
+            .map(|regex| Self {
+                regex,
+                message_id: None,
+            })
+            .map_err(|e| format!("Bad deny pattern {pattern:?}: {e}"))
+    }
+
+    /// Set the message id of the error, `rule.denied_pattern` is used otherwise.
+    pub fn message_id(mut self, message_id: &str) -> Self {
+        self.message_id = Some(message_id.to_string());
+        self
+    }
+
+    /// Get the regular expression.
+    pub fn as_str(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    /// Get the message id of the error, if set.
+    pub fn get_message_id(&self) -> Option<&str> {
+        self.message_id.as_deref()
+    }
+
+    /// Whether the password matches the pattern.
+    pub fn is_match(&self, password: impl AsRef<[u8]>) -> bool {
+        self.regex.is_match(password.as_ref())
+    }
+
+    /// Get the error reported for a password matching the pattern.
+    fn error(&self) -> PWQError {
+        let e = PWQError::rule(CODE, "The password matches a forbidden pattern")
+            .expect("the code of deny patterns is not reserved");
+
+        match &self.message_id {
+            Some(id) => e.with_message_id(id),
+            None => e,
+        }
+    }
+}
+
+impl PartialEq for DenyPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str() && self.message_id == other.message_id
+    }
+}
+
+impl Eq for DenyPattern {}
+
+impl std::fmt::Display for DenyPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(id) = &self.message_id {
+            write!(f, "{{{id}}} ")?;
+        }
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for DenyPattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        let Some(rest) = s.strip_prefix('{') else {
+            return Self::new(s);
+        };
+        let Some((id, pattern)) = rest.split_once('}') else {
+            return Err(format!("Unterminated message id in deny pattern: {s}"));
+        };
+
+        if id.is_empty() || id.contains(char::is_whitespace) {
+            return Err(format!("Bad message id {id:?} in deny pattern: {s}"));
+        }
+
+        Self::new(pattern.trim()).map(|p| p.message_id(id))
+    }
+}
+
+/// The files read by libpwquality for the given configuration file, in order.
+fn config_files(path: Option<&Path>) -> Vec<PathBuf> {
+    if let Some(path) = path {
+        return vec![path.to_path_buf()];
+    }
+
+    let mut files = vec![];
+
+    // the drop-in files are read before the default file since libpwquality 1.4.1
+    if cfg!(any(
+        feature = "v1_4_1",
+        feature = "vendored",
+        feature = "vendored-cracklib"
+    )) {
+        if let Ok(dir) = std::fs::read_dir(DROP_IN_DIR) {
+            files = dir
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "conf"))
+                .collect();
+            files.sort();
+        }
+    }
+    files.push(PathBuf::from(DEFAULT_CONFIG));
+
+    files
+}
+
+impl PWQuality {
+    /// Add a pattern that passwords must not match.
+    ///
+    /// The deny patterns are checked in order after the built-in checks of libpwquality
+    /// and before the custom rules. A matching password is rejected with an error of
+    /// kind [`ErrorKind::Rule`] and code `denied_pattern`.
+    pub fn deny_pattern(&self, pattern: DenyPattern) -> &Self {
+        self.deny_patterns.borrow_mut().push(pattern);
+        self
+    }
+
+    /// Get the patterns that passwords must not match.
+    pub fn get_deny_patterns(&self) -> Vec<DenyPattern> {
+        self.deny_patterns.borrow().clone()
+    }
+
+    /// Remove all the deny patterns.
+    pub fn clear_deny_patterns(&self) -> &Self {
+        self.deny_patterns.borrow_mut().clear();
+        self
+    }
+
+    /// Reject the password if it matches a deny pattern.
+    pub(crate) fn check_deny_patterns(&self, password: &[u8]) -> Result<()> {
+        match self
+            .deny_patterns
+            .borrow()
+            .iter()
+            .find(|pattern| pattern.is_match(password))
+        {
+            Some(pattern) => Err(pattern.error()),
+            None => Ok(()),
+        }
+    }
+
+    /// Add the deny pattern of a `denypattern=...` option, or return `None` for the
+    /// options of libpwquality.
+    pub(crate) fn set_deny_pattern_option(&self, option: &str) -> Option<Result<&Self>> {
+        if !is_deny_pattern_option(option) {
+            return None;
+        }
+        let (_, value) = option.split_once('=').unwrap_or((option, ""));
+
+        Some(
+            value
+                .parse()
+                .map(|pattern| self.deny_pattern(pattern))
+                .map_err(|e: String| PWQError::with_str(ErrorKind::CfgfileMalformed, &e)),
+        )
+    }
+
+    /// Read the configuration files again when libpwquality fails to, adding their deny
+    /// patterns.
+    ///
+    /// libpwquality rejects unknown settings, so when it fails with
+    /// `PWQ_ERROR_UNKNOWN_SETTING` or `PWQ_ERROR_CFGFILE_MALFORMED` and the files have
+    /// `denypattern` lines, each line of the files is applied in order with
+    /// [`set_option`](Self::set_option). Otherwise the error of libpwquality is returned,
+    /// so configurations without deny patterns are read by libpwquality only.
+    pub(crate) fn read_deny_pattern_config(
+        &self,
+        path: Option<&Path>,
+        error: PWQError,
+    ) -> Result<&Self> {
+        if !matches!(
+            error.kind(),
+            ErrorKind::UnknownSetting | ErrorKind::CfgfileMalformed
+        ) {
+            return Err(error);
+        }
+
+        let mut options = vec![];
+        for file in config_files(path) {
+            let text =
+                std::fs::read(&file).map_err(|_| PWQError::new(sys::PWQ_ERROR_CFGFILE_OPEN))?;
+            options.extend(String::from_utf8_lossy(&text).lines().filter_map(option));
+        }
+
+        if !options.iter().any(|option| is_deny_pattern_option(option)) {
+            return Err(error);
+        }
+
+        for option in &options {
+            self.set_option(option)?;
+        }
+
+        Ok(self)
+    }
+}
+
+/// Whether the option is a `denypattern=...` option.
+fn is_deny_pattern_option(option: &str) -> bool {
+    let key = option.split_once('=').map_or(option, |(key, _)| key);
+
+    key.trim() == DENY_PATTERN_KEY
+}
+
+/// Get the option of a line of a configuration file, as given to
+/// [`PWQuality::set_option`], or `None` for blank lines and comments.
+///
+/// Like libpwquality, the line ends at `#`, and the name is separated from the value by
+/// whitespace, `=` or both.
+fn option(line: &str) -> Option<String> {
+    let line = line.split('#').next().unwrap_or_default().trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    let end = line
+        .find(|c: char| c == '=' || c.is_ascii_whitespace())
+        .unwrap_or(line.len());
+    let (name, value) = line.split_at(end);
+    let value = value.trim_start();
+    let value = value.strip_prefix('=').unwrap_or(value).trim_start();
+
+    match value.is_empty() {
+        true => Some(name.to_string()),
+        false => Some(format!("{name}={value}")),
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{PWQuality, Result};
+
+/// Predefined password policies.
+///
+/// A preset only touches the strength settings, so it can be applied first and then
+/// overridden by site-specific values, e.g. with [`PWQuality::read_config`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Preset {
+    /// NIST SP 800-63B: long passwords checked against blocklists, no composition rules.
+    Nist80063b,
+    /// PCI-DSS 4.0 requirement 8.3.6: at least 12 characters with a digit and a lowercase
+    /// letter.
+    PciDss4,
+    /// CIS benchmark for Linux distributions: at least 14 characters from all four classes.
+    Cis,
+    /// The defaults that libpwquality ships in `pwquality.conf`.
+    Default,
+}
+
+// Not every setting is available with older libpwquality versions.
+#[allow(dead_code)]
+struct Values {
+    min_diff: i32,
+    min_length: i32,
+    digit_credit: i32,
+    uppercase_credit: i32,
+    lowercase_credit: i32,
+    other_credit: i32,
+    min_class: i32,
+    max_repeat: i32,
+    max_class_repeat: i32,
+    max_sequence: i32,
+    gecos_check: bool,
+    dict_check: bool,
+    user_check: bool,
+    enforcing: bool,
+    retry_times: i32,
+    enforce_for_root: bool,
+}
+
+const DEFAULT: Values = Values {
+    min_diff: 1,
+    min_length: 8,
+    digit_credit: 0,
+    uppercase_credit: 0,
+    lowercase_credit: 0,
+    other_credit: 0,
+    min_class: 0,
+    max_repeat: 0,
+    max_class_repeat: 0,
+    max_sequence: 0,
+    gecos_check: false,
+    dict_check: true,
+    user_check: true,
+    enforcing: true,
+    retry_times: 1,
+    enforce_for_root: false,
+};
+
+impl Preset {
+    /// All the presets.
+    pub const ALL: [Preset; 4] = [
+        Preset::Nist80063b,
+        Preset::PciDss4,
+        Preset::Cis,
+        Preset::Default,
+    ];
+
+    /// Get the name of the preset.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Nist80063b => "nist-800-63b",
+            Preset::PciDss4 => "pci-dss-4.0",
+            Preset::Cis => "cis",
+            Preset::Default => "default",
+        }
+    }
+
+    fn values(&self) -> Values {
+        match self {
+            // Composition rules and credits are disabled, the password is only
+            // required to be long and not to be found in any blocklist.
+            Preset::Nist80063b => Values {
+                min_length: 15,
+                max_repeat: 3,
+                max_sequence: 4,
+                gecos_check: true,
+                ..DEFAULT
+            },
+            // Negative credits can't lower the minimum length. libpwquality can't require
+            // a letter of either case, so a lowercase letter is required, which is
+            // stricter than the standard.
+            Preset::PciDss4 => Values {
+                min_length: 12,
+                digit_credit: -1,
+                lowercase_credit: -1,
+                retry_times: 3,
+                ..DEFAULT
+            },
+            Preset::Cis => Values {
+                min_diff: 2,
+                min_length: 14,
+                min_class: 4,
+                max_repeat: 3,
+                max_sequence: 3,
+                retry_times: 3,
+                enforce_for_root: true,
+                ..DEFAULT
+            },
+            Preset::Default => DEFAULT,
+        }
+    }
+}
+
+impl std::fmt::Display for Preset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for Preset {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Preset::ALL
+            .into_iter()
+            .find(|p| p.name().eq_ignore_ascii_case(s))
+            .ok_or(format!("Unknown preset: {s}"))
+    }
+}
+
+impl PWQuality {
+    /// Create a new `PWQuality` instance configured with the given preset.
+    pub fn with_preset(preset: Preset) -> Result<Self> {
+        let pwq = Self::new()?;
+        pwq.preset(preset)?;
+
+        Ok(pwq)
+    }
+
+    /// Configure the strength settings according to the given preset.
+    ///
+    /// The list of forbidden words is cleared, other settings such as the dictionary path
+    /// are left untouched.
+    pub fn preset(&self, preset: Preset) -> Result<&Self> {
+        let v = preset.values();
+
+        self.min_diff(v.min_diff)
+            .min_length(v.min_length)
+            .digit_credit(v.digit_credit)
+            .uppercase_credit(v.uppercase_credit)
+            .lowercase_credit(v.lowercase_credit)
+            .other_credit(v.other_credit)
+            .min_class(v.min_class)
+            .max_repeat(v.max_repeat)
+            .max_class_repeat(v.max_class_repeat)
+            .gecos_check(v.gecos_check);
+
+        #[cfg(any(feature = "v1_2", feature = "vendored", feature = "vendored-cracklib"))]
+        self.max_sequence(v.max_sequence);
+
+        #[cfg(any(feature = "v1_3", feature = "vendored", feature = "vendored-cracklib"))]
+        self.dict_check(v.dict_check);
+
+        #[cfg(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib"))]
+        self.user_check(v.user_check).enforcing(v.enforcing);
+
+        #[cfg(any(
+            feature = "v1_4_1",
+            feature = "vendored",
+            feature = "vendored-cracklib"
+        ))]
+        self.retry_times(v.retry_times)
+            .enforce_for_root(v.enforce_for_root);
+
+        self.bad_words(std::iter::empty::<&str>())
+    }
+}
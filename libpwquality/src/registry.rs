@@ -0,0 +1,103 @@
+use crate::Setting;
+
+/// Type of the value of a setting.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValueType {
+    /// An integer.
+    Int,
+    /// A boolean, `0` or `1` in the configuration file.
+    Bool,
+    /// A string.
+    Str,
+    /// A list of words separated by whitespace.
+    List,
+}
+
+impl std::fmt::Display for ValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ValueType::Int => "int",
+            ValueType::Bool => "bool",
+            ValueType::Str => "string",
+            ValueType::List => "list",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+/// Description of a setting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SettingInfo {
+    /// Key of the setting in the configuration file.
+    pub key: &'static str,
+    /// Name of the `PWQuality` setter, the getter is prefixed with `get_`.
+    pub accessor: &'static str,
+    /// Type of the value.
+    pub value_type: ValueType,
+    /// Whether the setting is enabled by its key alone, such as `enforce_for_root`.
+    pub bare: bool,
+    /// Default value as written in the configuration file.
+    pub default: &'static str,
+    /// Valid range of integer values, values out of the range are clamped by libpwquality.
+    pub range: Option<(i32, i32)>,
+    /// Meaning of negative values, if they differ from positive ones.
+    pub negative: Option<&'static str>,
+    /// Description of the setting.
+    pub description: &'static str,
+    /// The libpwquality version that introduced the setting.
+    pub since: &'static str,
+    /// Whether the setting is supported by the enabled features.
+    pub supported: bool,
+}
+
+impl SettingInfo {
+    /// All the settings known to this crate, including the ones not supported by the
+    /// enabled features.
+    pub fn all() -> &'static [SettingInfo] {
+        Self::ALL
+    }
+
+    /// Find a setting by its configuration file key.
+    pub fn find(key: &str) -> Option<&'static SettingInfo> {
+        Self::ALL.iter().find(|info| info.key == key)
+    }
+
+    /// Get the setting, `None` is returned if it's not supported by the enabled features.
+    pub fn setting(&self) -> Option<Setting> {
+        self.key.parse().ok()
+    }
+
+    /// Check a value as written in the configuration file, `None` being a bare key.
+    ///
+    /// Values that libpwquality accepts but clamps to the range are rejected.
+    pub fn validate(&self, value: Option<&str>) -> Result<(), String> {
+        let value = value.map(str::trim).unwrap_or_default();
+
+        match self.value_type {
+            _ if self.bare => Ok(()),
+            ValueType::Int | ValueType::Bool if value.is_empty() => {
+                Err("missing value".to_string())
+            }
+            ValueType::Int => {
+                let v = value
+                    .parse::<i32>()
+                    .map_err(|_| format!("{value:?} is not an integer"))?;
+
+                match self.range {
+                    Some((min, _)) if v < min => Err(format!("{v} is less than the minimum {min}")),
+                    Some((_, max)) if v > max => {
+                        Err(format!("{v} is greater than the maximum {max}"))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            ValueType::Bool => match value.parse::<i32>() {
+                Ok(0 | 1) => Ok(()),
+                Ok(v) => Err(format!("{v} is neither 0 nor 1")),
+                Err(_) => Err(format!("{value:?} is not an integer")),
+            },
+            ValueType::Str | ValueType::List => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{Catalog, PWQuality};
+
+/// Class of characters.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CharClass {
+    /// Digits.
+    Digit,
+    /// Uppercase letters.
+    Uppercase,
+    /// Lowercase letters.
+    Lowercase,
+    /// Any other character.
+    Other,
+}
+
+impl CharClass {
+    /// All the classes.
+    pub const ALL: [CharClass; 4] = [
+        CharClass::Digit,
+        CharClass::Uppercase,
+        CharClass::Lowercase,
+        CharClass::Other,
+    ];
+
+    /// Get the class of a character, as libpwquality classifies it.
+    pub fn of(c: char) -> Self {
+        if c.is_ascii_digit() {
+            CharClass::Digit
+        } else if c.is_uppercase() {
+            CharClass::Uppercase
+        } else if c.is_lowercase() {
+            CharClass::Lowercase
+        } else {
+            CharClass::Other
+        }
+    }
+
+    /// Get the class of a byte of a password.
+    ///
+    /// libpwquality classifies the bytes, so every byte of a non-ASCII character counts as
+    /// an other character.
+    pub(crate) fn of_byte(b: u8) -> Self {
+        if b.is_ascii() {
+            Self::of(b as char)
+        } else {
+            CharClass::Other
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            CharClass::Digit => "digits",
+            CharClass::Uppercase => "uppercase",
+            CharClass::Lowercase => "lowercase",
+            CharClass::Other => "other",
+        }
+    }
+}
+
+/// A requirement that passwords must meet according to the settings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum Requirement {
+    /// The password must be at least `length` characters long, where characters earning
+    /// a credit count twice so that `effective` characters can be enough.
+    MinLength { length: i32, effective: i32 },
+    /// The password must contain `count` characters of the class, from a negative credit.
+    MinChars { class: CharClass, count: i32 },
+    /// Up to `credit` characters of the class count as an extra character each.
+    Credit { class: CharClass, credit: i32 },
+    /// The password must contain characters from at least this number of classes.
+    MinClass(i32),
+    /// No more than this number of identical consecutive characters.
+    MaxRepeat(i32),
+    /// No more than this number of consecutive characters of the same class.
+    MaxClassRepeat(i32),
+    /// No monotonic character sequence longer than this number of characters.
+    MaxSequence(i32),
+    /// The password must differ from the old one by at least this number of characters.
+    MinDiff(i32),
+    /// The password must not be a palindrome.
+    NotPalindrome,
+    /// The password must not be a part of the old one repeated twice, which includes its
+    /// rotations, nor the old one with only case changes.
+    NotOldVariant,
+    /// The password must not be based on a dictionary word.
+    NoDictionaryWord,
+    /// The password must not contain the user name.
+    NoUserName,
+    /// The password must not contain substrings of the user name of this length, forwards
+    /// or reversed. Like libpwquality, lengths of 3 or less are not checked.
+    UserSubstr(i32),
+    /// The password must not contain words from the GECOS field of the user.
+    NoGecos,
+    /// The password must not contain any of the words.
+    NoBadWords(Vec<String>),
+}
+
+impl Requirement {
+    /// Get the id of the message describing the requirement and its arguments.
+    pub fn message(&self) -> (String, Vec<(&'static str, String)>) {
+        let n = |n: &i32| vec![("n", n.to_string())];
+
+        match self {
+            Requirement::MinLength { length, effective } if effective < length => (
+                "requirement.min_length.credits".to_string(),
+                vec![
+                    ("n", length.to_string()),
+                    ("effective", effective.to_string()),
+                ],
+            ),
+            Requirement::MinLength { length, .. } => {
+                ("requirement.min_length".to_string(), n(length))
+            }
+            Requirement::MinChars { class, count } => {
+                (format!("requirement.min_{}", class.name()), n(count))
+            }
+            Requirement::Credit { class, credit } => {
+                (format!("requirement.credit_{}", class.name()), n(credit))
+            }
+            Requirement::MinClass(v) => ("requirement.min_class".to_string(), n(v)),
+            Requirement::MaxRepeat(v) => ("requirement.max_repeat".to_string(), n(v)),
+            Requirement::MaxClassRepeat(v) => ("requirement.max_class_repeat".to_string(), n(v)),
+            Requirement::MaxSequence(v) => ("requirement.max_sequence".to_string(), n(v)),
+            Requirement::MinDiff(v) => ("requirement.min_diff".to_string(), n(v)),
+            Requirement::NotPalindrome => ("requirement.not_palindrome".to_string(), vec![]),
+            Requirement::NotOldVariant => ("requirement.not_old_variant".to_string(), vec![]),
+            Requirement::NoDictionaryWord => ("requirement.no_dictionary_word".to_string(), vec![]),
+            Requirement::NoUserName => ("requirement.no_user_name".to_string(), vec![]),
+            Requirement::UserSubstr(v) => ("requirement.user_substr".to_string(), n(v)),
+            Requirement::NoGecos => ("requirement.no_gecos".to_string(), vec![]),
+            Requirement::NoBadWords(words) => (
+                "requirement.no_bad_words".to_string(),
+                vec![("n", words.len().to_string()), ("words", words.join(", "))],
+            ),
+        }
+    }
+
+    /// Describe the requirement with the messages of the catalog.
+    pub fn text(&self, catalog: &Catalog) -> String {
+        let (id, args) = self.message();
+
+        catalog.format(&id, &args)
+    }
+}
+
+impl std::fmt::Display for Requirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text(&Catalog::default()))
+    }
+}
+
+impl PWQuality {
+    /// Get the requirements that passwords must meet according to the settings.
+    pub fn requirements(&self) -> Vec<Requirement> {
+        let mut requirements = vec![Requirement::MinLength {
+            length: self.get_min_length(),
+            effective: self.effective_min_length(),
+        }];
+
+        let credits = [
+            self.get_digit_credit(),
+            self.get_uppercase_credit(),
+            self.get_lowercase_credit(),
+            self.get_other_credit(),
+        ];
+        for (class, credit) in CharClass::ALL.into_iter().zip(credits) {
+            if credit < 0 {
+                requirements.push(Requirement::MinChars {
+                    class,
+                    count: -credit,
+                });
+            } else if credit > 0 {
+                requirements.push(Requirement::Credit { class, credit });
+            }
+        }
+
+        let min_class = self.get_min_class();
+        if min_class > 1 {
+            requirements.push(Requirement::MinClass(min_class));
+        }
+
+        let max_repeat = self.get_max_repeat();
+        if max_repeat > 0 {
+            requirements.push(Requirement::MaxRepeat(max_repeat));
+        }
+
+        let max_class_repeat = self.get_max_class_repeat();
+        if max_class_repeat > 0 {
+            requirements.push(Requirement::MaxClassRepeat(max_class_repeat));
+        }
+
+        #[cfg(any(feature = "v1_2", feature = "vendored", feature = "vendored-cracklib"))]
+        if self.get_max_sequence() > 0 {
+            requirements.push(Requirement::MaxSequence(self.get_max_sequence()));
+        }
+
+        let min_diff = self.get_min_diff();
+        if min_diff > 0 {
+            requirements.push(Requirement::MinDiff(min_diff));
+        }
+
+        requirements.push(Requirement::NotPalindrome);
+        requirements.push(Requirement::NotOldVariant);
+
+        #[cfg(any(feature = "v1_3", feature = "vendored", feature = "vendored-cracklib"))]
+        let dict_check = self.get_dict_check();
+        #[cfg(not(any(feature = "v1_3", feature = "vendored", feature = "vendored-cracklib")))]
+        let dict_check = true;
+
+        if dict_check {
+            requirements.push(Requirement::NoDictionaryWord);
+        }
+
+        #[cfg(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib"))]
+        let user_check = self.get_user_check();
+        #[cfg(not(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib")))]
+        let user_check = true;
+
+        if user_check {
+            requirements.push(Requirement::NoUserName);
+
+            #[cfg(any(
+                feature = "v1_4_5",
+                feature = "vendored",
+                feature = "vendored-cracklib"
+            ))]
+            if self.get_user_substr() > 3 {
+                requirements.push(Requirement::UserSubstr(self.get_user_substr()));
+            }
+        }
+
+        if self.get_gecos_check() {
+            requirements.push(Requirement::NoGecos);
+        }
+
+        let bad_words = self.get_bad_words().unwrap_or_default();
+        if !bad_words.is_empty() {
+            requirements.push(Requirement::NoBadWords(bad_words));
+        }
+
+        requirements
+    }
+
+    /// Describe the requirements in plain language with the messages of the catalog.
+    pub fn describe(&self, catalog: &Catalog) -> Vec<String> {
+        self.requirements()
+            .iter()
+            .map(|requirement| requirement.text(catalog))
+            .collect()
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{PWQError, PWQuality, Result};
+use std::rc::Rc;
+
+/// A custom rule checked along with the built-in checks of libpwquality.
+///
+/// Rules registered with [`PWQuality::add_rule`] run in order on the passwords that pass
+/// the built-in checks, by [`PWQuality::check`] and every other check method. A rule
+/// rejects a password with an error created by [`PWQError::rule`], or adjusts its score.
+///
+/// Closures taking the password, the old password and the user name are rules.
+pub trait Rule {
+    /// Check the password, returning the adjustment of the score.
+    ///
+    /// The score stays between 0 and 100 whatever the adjustments.
+    fn check(&self, password: &str, old_password: Option<&str>, user: Option<&str>) -> Result<i32>;
+}
+
+impl<F> Rule for F
+where
+    F: Fn(&str, Option<&str>, Option<&str>) -> std::result::Result<i32, PWQError>,
+{
+    fn check(&self, password: &str, old_password: Option<&str>, user: Option<&str>) -> Result<i32> {
+        self(password, old_password, user)
+    }
+}
+
+impl PWQuality {
+    /// Add a custom rule, run after the built-in checks and the rules added before.
+    ///
+    /// A rule may add or remove rules, which only applies to the following checks.
+    pub fn add_rule(&self, rule: impl Rule + 'static) -> &Self {
+        self.rules.borrow_mut().push(Rc::new(rule));
+        self
+    }
+
+    /// Remove all the custom rules.
+    pub fn clear_rules(&self) -> &Self {
+        self.rules.borrow_mut().clear();
+        self
+    }
+
+    /// Run the custom rules on a password that passed the built-in checks with the score.
+    ///
+    /// Passwords that aren't valid UTF-8 are given to the rules lossily converted.
+    pub(crate) fn check_rules(
+        &self,
+        password: &[u8],
+        old_password: Option<&[u8]>,
+        user: Option<&[u8]>,
+        score: i32,
+    ) -> Result<i32> {
+        // the rules run on a copy of the list, so that they can change it
+        let rules = self.rules.borrow().clone();
+        if rules.is_empty() {
+            return Ok(score);
+        }
+
+        let password = String::from_utf8_lossy(password);
+        let old_password = old_password.map(String::from_utf8_lossy);
+        let user = user.map(String::from_utf8_lossy);
+
+        rules.iter().try_fold(score, |score, rule| {
+            let adjustment = rule.check(&password, old_password.as_deref(), user.as_deref())?;
+
+            Ok(score.saturating_add(adjustment).clamp(0, 100))
+        })
+    }
+}
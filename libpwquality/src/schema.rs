@@ -0,0 +1,76 @@
+use crate::json::json_string;
+use crate::{SettingInfo, ValueType};
+
+/// Get the JSON Schema properties of a setting.
+///
+/// The description is the one of the `define_settings!` table, which also documents the
+/// getters and setters, so the schema and the API documentation can't disagree.
+fn property(info: &SettingInfo) -> Vec<(&'static str, String)> {
+    let mut description = info.description.to_string();
+    if let Some(negative) = info.negative {
+        description += &format!(" If negative, {negative}.");
+    }
+
+    let mut property = vec![("description", json_string(&description))];
+
+    match info.value_type {
+        ValueType::Int => {
+            property.push(("type", json_string("integer")));
+            if let Some((min, max)) = info.range {
+                property.push(("minimum", min.to_string()));
+                property.push(("maximum", max.to_string()));
+            }
+            property.push(("default", info.default.to_string()));
+        }
+        ValueType::Bool => {
+            property.push(("type", json_string("boolean")));
+            property.push(("default", (info.default != "0").to_string()));
+        }
+        ValueType::Str => {
+            property.push(("type", json_string("string")));
+            property.push(("default", json_string(info.default)));
+        }
+        ValueType::List => {
+            property.push(("type", json_string("array")));
+            property.push((
+                "items",
+                "{ \"type\": \"string\", \"pattern\": \"^\\\\S+$\" }".to_string(),
+            ));
+            property.push(("default", "[]".to_string()));
+        }
+    }
+
+    property
+}
+
+/// Generate the JSON Schema of a password policy.
+///
+/// A policy is an object whose properties are the configuration file keys of the
+/// settings supported by the enabled features. Booleans are JSON booleans and the bad
+/// words are an array of strings.
+pub fn json_schema() -> String {
+    let properties = SettingInfo::all()
+        .iter()
+        .filter(|info| info.supported)
+        .map(|info| {
+            let fields = property(info)
+                .into_iter()
+                .map(|(name, value)| format!("      {}: {value}", json_string(name)))
+                .collect::<Vec<_>>()
+                .join(",\n");
+
+            format!("    {}: {{\n{fields}\n    }}", json_string(info.key))
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    format!(
+        "{{\n  \
+         \"$schema\": \"https://json-schema.org/draft/2020-12/schema\",\n  \
+         \"title\": \"pwquality policy\",\n  \
+         \"type\": \"object\",\n  \
+         \"properties\": {{\n{properties}\n  }},\n  \
+         \"additionalProperties\": false\n\
+         }}\n"
+    )
+}
@@ -0,0 +1,37 @@
+use crate::{Aux, ErrorKind, PWQError};
+use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+
+impl Serialize for ErrorKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.stable_code())
+    }
+}
+
+/// Parameters of an error, as a map of a single entry or an empty map.
+struct Params<'a>(&'a PWQError);
+
+impl Serialize for Params<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let param = self.0.param();
+        let mut map = serializer.serialize_map(Some(usize::from(param.is_some())))?;
+
+        if let Some((name, value)) = param {
+            match self.0.aux {
+                Aux::Int(v) => map.serialize_entry(name, &v)?,
+                _ => map.serialize_entry(name, &value)?,
+            }
+        }
+
+        map.end()
+    }
+}
+
+impl Serialize for PWQError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("PWQError", 3)?;
+        s.serialize_field("code", self.stable_code())?;
+        s.serialize_field("message", &self.to_string())?;
+        s.serialize_field("params", &Params(self))?;
+        s.end()
+    }
+}
@@ -0,0 +1,181 @@
+use crate::checklist::{contains, distance, widen};
+use crate::{Catalog, ErrorKind, PWQuality};
+
+/// Metric deciding whether a password is too similar to the old one, with its threshold.
+///
+/// Like libpwquality, the passwords are compared case-insensitively.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Similarity {
+    /// Too similar with fewer than this number of insertions, deletions and substitutions.
+    Levenshtein(usize),
+    /// Too similar with fewer than this number of insertions, deletions, substitutions and
+    /// transpositions of adjacent characters.
+    Damerau(usize),
+    /// Too similar when the longest common substring is at least this fraction of the
+    /// length of the password, between 0 and 1.
+    CommonSubstring(f64),
+}
+
+/// Why a password is too similar to the old one.
+///
+/// The reason is attached to the errors of [`PWQuality::check_with`], see
+/// [`PWQError::similarity_reason`](crate::PWQError::similarity_reason).
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum SimilarityReason {
+    /// The password is the same as the old one.
+    Same,
+    /// The password differs from the old one with case changes only.
+    CaseSwapped,
+    /// The password is the old one reversed.
+    Reversed,
+    /// The password is a part of the old one repeated twice, such as a rotation.
+    Rotated,
+    /// The password is this number of edits away from the old one.
+    Edits(usize),
+    /// The password shares the substring with the old one, making up this fraction of
+    /// its length.
+    CommonSubstring { substring: String, ratio: f64 },
+}
+
+impl SimilarityReason {
+    /// Get the id of the message describing the reason and its arguments.
+    pub fn message(&self) -> (String, Vec<(&'static str, String)>) {
+        match self {
+            SimilarityReason::Same => ("similarity.same".to_string(), vec![]),
+            SimilarityReason::CaseSwapped => ("similarity.case_swapped".to_string(), vec![]),
+            SimilarityReason::Reversed => ("similarity.reversed".to_string(), vec![]),
+            SimilarityReason::Rotated => ("similarity.rotated".to_string(), vec![]),
+            SimilarityReason::Edits(n) => {
+                ("similarity.edits".to_string(), vec![("n", n.to_string())])
+            }
+            SimilarityReason::CommonSubstring { substring, ratio } => (
+                "similarity.common_substring".to_string(),
+                vec![
+                    ("part", substring.clone()),
+                    ("percent", format!("{:.0}", ratio * 100.0)),
+                ],
+            ),
+        }
+    }
+
+    /// Describe the reason with the messages of the catalog.
+    pub fn text(&self, catalog: &Catalog) -> String {
+        let (id, args) = self.message();
+
+        catalog.format(&id, &args)
+    }
+
+    /// Get the kind of error reported by libpwquality for the reason.
+    pub(crate) fn kind(&self) -> ErrorKind {
+        match self {
+            SimilarityReason::Same => ErrorKind::SamePassword,
+            SimilarityReason::CaseSwapped => ErrorKind::CaseChangesOnly,
+            SimilarityReason::Rotated => ErrorKind::Rotated,
+            _ => ErrorKind::TooSimilar,
+        }
+    }
+}
+
+// the ratio is never NaN
+impl Eq for SimilarityReason {}
+
+impl std::fmt::Display for SimilarityReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text(&Catalog::default()))
+    }
+}
+
+/// Optimal string alignment distance, where a transposition of adjacent characters
+/// counts as a single edit.
+fn damerau(a: &[u8], b: &[u8]) -> usize {
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+
+    rows[a.len()][b.len()]
+}
+
+/// Range in `a` of the longest substring common to both byte strings.
+fn longest_common_substring(a: &[u8], b: &[u8]) -> std::ops::Range<usize> {
+    let mut longest = 0..0;
+    let mut row = vec![0; b.len() + 1];
+
+    for (i, &ca) in a.iter().enumerate() {
+        for j in (0..b.len()).rev() {
+            row[j + 1] = if ca == b[j] { row[j] + 1 } else { 0 };
+            if row[j + 1] > longest.len() {
+                longest = i + 1 - row[j + 1]..i + 1;
+            }
+        }
+    }
+
+    longest
+}
+
+impl PWQuality {
+    /// Explain why the password is too similar to the old one according to the metrics,
+    /// or return `None` if it isn't.
+    ///
+    /// Same passwords, case changes, reversals and rotations are always detected, then
+    /// each metric is evaluated in order. The `min_diff` setting isn't taken into
+    /// account, add a [`Similarity::Levenshtein`] metric for it.
+    pub fn similarity(
+        password: &str,
+        old_password: &str,
+        metrics: &[Similarity],
+    ) -> Option<SimilarityReason> {
+        if password == old_password {
+            return Some(SimilarityReason::Same);
+        }
+
+        let new = password.as_bytes().to_ascii_lowercase();
+        let old = old_password.as_bytes().to_ascii_lowercase();
+
+        if new == old {
+            return Some(SimilarityReason::CaseSwapped);
+        }
+        if new.iter().eq(old.iter().rev()) {
+            return Some(SimilarityReason::Reversed);
+        }
+        if contains(&[old.as_slice(), old.as_slice()].concat(), &new) {
+            return Some(SimilarityReason::Rotated);
+        }
+
+        metrics.iter().find_map(|metric| match *metric {
+            Similarity::Levenshtein(min) => {
+                let edits = distance(&old, &new);
+                (edits < min).then_some(SimilarityReason::Edits(edits))
+            }
+            Similarity::Damerau(min) => {
+                let edits = damerau(&old, &new);
+                (edits < min).then_some(SimilarityReason::Edits(edits))
+            }
+            Similarity::CommonSubstring(threshold) => {
+                // lowercasing keeps the positions of the characters
+                let common = longest_common_substring(&new, &old);
+                let ratio = common.len() as f64 / new.len().max(1) as f64;
+
+                (!common.is_empty() && ratio >= threshold).then(|| {
+                    SimilarityReason::CommonSubstring {
+                        substring: password[widen(password, common)].to_string(),
+                        ratio,
+                    }
+                })
+            }
+        })
+    }
+}
@@ -0,0 +1,322 @@
+use crate::checklist::{distance, find, longest_run, longest_sequence, user_substr, widen, Input};
+use crate::{Catalog, CharClass, PWQuality, Requirement, Status};
+use std::ops::Range;
+
+/// Concrete change that improves a password.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum Advice {
+    /// Add this number of characters.
+    AddCharacters(i32),
+    /// Add this number of characters of the class.
+    AddClassChars { class: CharClass, count: i32 },
+    /// Add characters of this number of the given missing classes.
+    AddClasses { classes: Vec<CharClass>, count: i32 },
+    /// Avoid repeating the same character, as in the given part of the password.
+    AvoidRepeat(String),
+    /// Mix characters of other classes into the given part of the password.
+    AvoidClassRun(String),
+    /// Avoid the given monotonic sequence.
+    AvoidSequence(String),
+    /// Don't reuse parts of the user name.
+    AvoidUserName,
+    /// Avoid the given forbidden word.
+    AvoidWord(String),
+    /// Don't use a palindrome.
+    AvoidPalindrome,
+    /// Don't reuse the old password.
+    AvoidOldPassword,
+    /// Change this number of characters more from the old password.
+    ChangeMore(i32),
+}
+
+impl Advice {
+    /// Get the id of the message describing the advice and its arguments.
+    pub fn message(&self, catalog: &Catalog) -> (String, Vec<(&'static str, String)>) {
+        let n = |n: &i32| vec![("n", n.to_string())];
+
+        match self {
+            Advice::AddCharacters(v) => ("suggestion.add_characters".to_string(), n(v)),
+            Advice::AddClassChars { class, count } => {
+                (format!("suggestion.add_{}", class.name()), n(count))
+            }
+            Advice::AddClasses { classes, count } => {
+                let names = classes
+                    .iter()
+                    .map(|class| catalog.format(&format!("class.{}", class.name()), &[]))
+                    .collect::<Vec<_>>();
+                let list = match names.split_last() {
+                    Some((last, rest)) if !rest.is_empty() => catalog.format(
+                        "list.or",
+                        &[("first", rest.join(", ")), ("last", last.clone())],
+                    ),
+                    _ => names.concat(),
+                };
+
+                (
+                    "suggestion.add_classes".to_string(),
+                    vec![("n", count.to_string()), ("classes", list)],
+                )
+            }
+            Advice::AvoidRepeat(part) => (
+                "suggestion.avoid_repeat".to_string(),
+                vec![("part", part.clone())],
+            ),
+            Advice::AvoidClassRun(part) => (
+                "suggestion.avoid_class_run".to_string(),
+                vec![("part", part.clone())],
+            ),
+            Advice::AvoidSequence(part) => (
+                "suggestion.avoid_sequence".to_string(),
+                vec![("part", part.clone())],
+            ),
+            Advice::AvoidUserName => ("suggestion.avoid_user_name".to_string(), vec![]),
+            Advice::AvoidWord(word) => (
+                "suggestion.avoid_word".to_string(),
+                vec![("word", word.clone())],
+            ),
+            Advice::AvoidPalindrome => ("suggestion.avoid_palindrome".to_string(), vec![]),
+            Advice::AvoidOldPassword => ("suggestion.avoid_old_password".to_string(), vec![]),
+            Advice::ChangeMore(v) => ("suggestion.change_more".to_string(), n(v)),
+        }
+    }
+}
+
+/// A suggestion to improve a password.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Suggestion {
+    /// The change to make.
+    pub advice: Advice,
+    /// Whether the change is needed to satisfy a requirement of the settings.
+    pub required: bool,
+    /// Score given by [`PWQuality::check`] to the password with the change made, 0 if
+    /// the password is still rejected.
+    pub score: i32,
+}
+
+impl Suggestion {
+    /// Describe the suggestion with the messages of the catalog.
+    pub fn text(&self, catalog: &Catalog) -> String {
+        let (id, args) = self.advice.message(catalog);
+
+        catalog.format(&id, &args)
+    }
+}
+
+impl std::fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text(&Catalog::default()))
+    }
+}
+
+/// Characters of each class used to change the password, without repeats or sequences.
+const SAMPLES: [&[u8; 4]; 4] = [b"7305", b"KQXM", b"wjzv", b"#%!&"];
+
+/// Get the `n`th character of the class used to change the password.
+fn sample(class: CharClass, n: usize) -> char {
+    SAMPLES[class as usize][n % 4] as char
+}
+
+/// Get the `n`th replacement of a character, of another class than the character.
+fn filler(c: char, n: usize) -> char {
+    let class = CharClass::ALL[(CharClass::of(c) as usize + 1 + n % 3) % 4];
+
+    sample(class, n / 3)
+}
+
+/// Replace every `step`th character of the byte range of the password.
+fn replace(password: &str, range: Range<usize>, step: usize) -> String {
+    let range = widen(password, range);
+    let part = password[range.clone()]
+        .chars()
+        .enumerate()
+        .map(|(n, c)| {
+            if n % step == step - 1 {
+                filler(c, n)
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+
+    format!(
+        "{}{part}{}",
+        &password[..range.start],
+        &password[range.end..]
+    )
+}
+
+/// Append characters of the classes to the password.
+fn append(password: &str, classes: impl IntoIterator<Item = CharClass>) -> String {
+    let mut candidate = password.to_string();
+    candidate.extend(classes.into_iter().enumerate().map(|(n, c)| sample(c, n)));
+
+    candidate
+}
+
+fn missing_classes(input: &Input) -> Vec<CharClass> {
+    CharClass::ALL
+        .into_iter()
+        .filter(|&class| input.counts[class as usize] == 0)
+        .collect()
+}
+
+/// Get the suggestion fixing an unmet requirement, and the password with the fix.
+fn fix(requirement: &Requirement, password: &str, input: &Input) -> Option<(Advice, String)> {
+    let part = |range: Range<usize>| password[widen(password, range)].to_string();
+
+    let fix = match requirement {
+        Requirement::MinLength { length, .. } => {
+            let count = length - input.earned_credits() - input.password.len() as i32;
+            let last = password.chars().last().unwrap_or_default();
+            let candidate = password
+                .chars()
+                .chain((0..count as usize).map(|n| filler(last, n)))
+                .collect();
+
+            (Advice::AddCharacters(count), candidate)
+        }
+        Requirement::MinChars { class, count } => {
+            let count = count - input.counts[*class as usize];
+
+            (
+                Advice::AddClassChars {
+                    class: *class,
+                    count,
+                },
+                append(password, std::iter::repeat_n(*class, count as usize)),
+            )
+        }
+        Requirement::MinClass(min) => {
+            let classes = missing_classes(input);
+            let count = min - (4 - classes.len() as i32);
+            let candidate = append(password, classes.iter().take(count as usize).copied());
+
+            (Advice::AddClasses { classes, count }, candidate)
+        }
+        Requirement::MaxRepeat(_) => {
+            let run = longest_run(input.password);
+
+            (
+                Advice::AvoidRepeat(part(run.clone())),
+                replace(password, run, 2),
+            )
+        }
+        Requirement::MaxClassRepeat(_) => {
+            let classes = input
+                .password
+                .iter()
+                .map(|&b| CharClass::of_byte(b))
+                .collect::<Vec<_>>();
+            let run = longest_run(&classes);
+
+            (
+                Advice::AvoidClassRun(part(run.clone())),
+                replace(password, run, 2),
+            )
+        }
+        Requirement::MaxSequence(_) => {
+            let sequence = longest_sequence(input.password);
+
+            (
+                Advice::AvoidSequence(part(sequence.clone())),
+                replace(password, sequence, 2),
+            )
+        }
+        Requirement::MinDiff(min) => {
+            let old = input.old.as_ref()?;
+            let count = *min - distance(old, &input.lower) as i32;
+            let end = password
+                .char_indices()
+                .nth(count as usize)
+                .map_or(password.len(), |(i, _)| i);
+
+            (Advice::ChangeMore(count), replace(password, 0..end, 1))
+        }
+        Requirement::NotPalindrome => (
+            Advice::AvoidPalindrome,
+            replace(password, 0..password.len(), password.chars().count().max(1)),
+        ),
+        Requirement::NotOldVariant => (
+            Advice::AvoidOldPassword,
+            replace(password, 0..password.len(), 2),
+        ),
+        Requirement::NoUserName | Requirement::UserSubstr(_) => {
+            let user = input.user.as_ref()?;
+            let length = match requirement {
+                Requirement::UserSubstr(length) => (*length as usize).clamp(1, user.len().max(1)),
+                _ => user.len().max(1),
+            };
+            let range = user_substr(&input.lower, user, length).unwrap_or(0..input.password.len());
+
+            (Advice::AvoidUserName, replace(password, range, 1))
+        }
+        Requirement::NoBadWords(words) => {
+            let (word, range) = words.iter().find_map(|word| {
+                (word.len() > 3)
+                    .then(|| find(&input.lower, word.to_ascii_lowercase().as_bytes()))
+                    .flatten()
+                    .map(|range| (word, range))
+            })?;
+
+            (Advice::AvoidWord(word.clone()), replace(password, range, 1))
+        }
+        _ => return None,
+    };
+
+    Some(fix)
+}
+
+impl PWQuality {
+    /// Suggest changes that improve the password, ranked by how much they help.
+    ///
+    /// The changes needed to satisfy the requirements of the settings come first, followed
+    /// by optional improvements. Each change is made to the password, and the changes are
+    /// ordered by the score [`check`](Self::check) gives to the changed password, then in
+    /// the order of the requirements. Like [`checklist`](Self::checklist), the requirements
+    /// that need the cracklib dictionary or the passwd file are not evaluated.
+    pub fn suggestions(
+        &self,
+        password: &str,
+        old_password: Option<&str>,
+        user: Option<&str>,
+    ) -> Vec<Suggestion> {
+        let input = Input::new(self, password, old_password, user);
+        let score = |candidate: &str| self.check(candidate, old_password, user).unwrap_or(0);
+
+        let mut suggestions = self
+            .requirements()
+            .iter()
+            .filter(|requirement| requirement.status(&input) == Status::Unmet)
+            .filter_map(|requirement| fix(requirement, password, &input))
+            .map(|(advice, candidate)| Suggestion {
+                advice,
+                required: true,
+                score: score(&candidate),
+            })
+            .collect::<Vec<_>>();
+
+        // using more classes is worth it even when not required
+        let classes = missing_classes(&input);
+        if !classes.is_empty()
+            && !suggestions
+                .iter()
+                .any(|s| matches!(s.advice, Advice::AddClasses { .. }))
+        {
+            let candidate = append(password, [classes[0]]);
+
+            suggestions.push(Suggestion {
+                advice: Advice::AddClasses { classes, count: 1 },
+                required: false,
+                score: score(&candidate),
+            });
+        }
+
+        suggestions.sort_by(|a, b| b.required.cmp(&a.required).then(b.score.cmp(&a.score)));
+
+        suggestions
+    }
+}
@@ -1,4 +1,8 @@
-use libpwquality::PWQuality;
+use libpwquality::{
+    json_schema, lint_config, migrate_pam, Advice, Catalog, CharClass, CheckContext, ConfigFile,
+    ErrorKind, PWQError, PWQuality, PasswdEntry, Preset, Requirement, Rule, Setting, SettingInfo,
+    Severity, Similarity, SimilarityReason, Standard, Status, UserContext, ValueType, DROP_IN_DIR,
+};
 
 const MAX: i32 = 10;
 
@@ -257,3 +261,1049 @@ fn test_local_users_only() {
         assert_eq!(pwq.get_local_users_only(), value);
     }
 }
+
+#[test]
+fn test_generate_bytes() {
+    let pwq = PWQuality::new().unwrap();
+    let password = pwq.generate_bytes(32).unwrap();
+
+    assert!(!password.is_empty());
+    assert!(!password.contains(&0));
+}
+
+#[test]
+fn test_check_bytes() {
+    let pwq = PWQuality::new().unwrap();
+    let score = pwq.check_bytes(b"p@s5w0rD!", None, None).unwrap();
+    assert_eq!(score, pwq.check("p@s5w0rD!", None, None).unwrap());
+
+    // ISO-8859-1 encoded "p@s5wörD!"
+    let score = pwq
+        .check_bytes(b"p@s5w\xf6rD!", Some(b"password"), Some(b"root"))
+        .unwrap();
+    assert!(score >= 0);
+
+    let ret = pwq.check_bytes(b"p@s5w0rD!", Some(b"p@s5w0rD!"), None);
+    assert!(ret.is_err());
+}
+
+#[test]
+fn test_check_os_str() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let pwq = PWQuality::new().unwrap();
+    let password = OsStr::from_bytes(b"p@s5w\xf6rD!");
+    let score = pwq
+        .check_os_str(password, None, Some(OsStr::new("root")))
+        .unwrap();
+    assert_eq!(
+        score,
+        pwq.check_bytes(password.as_bytes(), None, Some(b"root"))
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_preset() {
+    let pwq = PWQuality::with_preset(Preset::Cis).unwrap();
+    assert_eq!(pwq.get_min_length(), 14);
+    assert_eq!(pwq.get_min_class(), 4);
+
+    pwq.bad_words(["bad", "words"]).unwrap();
+    pwq.preset(Preset::Nist80063b).unwrap();
+    assert_eq!(pwq.get_min_length(), 15);
+    assert_eq!(pwq.get_min_class(), 0);
+    assert_eq!(pwq.get_digit_credit(), 0);
+    assert!(pwq.get_bad_words().unwrap().is_empty());
+
+    for preset in Preset::ALL {
+        assert_eq!(preset.name().parse::<Preset>().unwrap(), preset);
+    }
+}
+
+#[test]
+fn test_preset_with_config() {
+    let path = std::env::temp_dir().join(format!(
+        "libpwquality-test-preset-{}.conf",
+        std::process::id()
+    ));
+    std::fs::write(&path, "minlen = 20\n").unwrap();
+
+    let pwq = PWQuality::new().unwrap();
+    pwq.preset(Preset::PciDss4)
+        .unwrap()
+        .read_config(&path)
+        .unwrap();
+
+    assert_eq!(pwq.get_min_length(), 20);
+    assert_eq!(pwq.get_digit_credit(), -1);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_effective_min_length() {
+    let pwq = PWQuality::new().unwrap();
+    pwq.min_length(12)
+        .digit_credit(0)
+        .uppercase_credit(0)
+        .lowercase_credit(0)
+        .other_credit(0);
+    assert_eq!(pwq.effective_min_length(), 12);
+
+    pwq.digit_credit(1).other_credit(2);
+    assert_eq!(pwq.effective_min_length(), 9);
+
+    pwq.uppercase_credit(9);
+    assert_eq!(pwq.effective_min_length(), 6);
+}
+
+#[test]
+fn test_audit() {
+    let pwq = PWQuality::with_preset(Preset::Cis).unwrap();
+    assert!(!pwq.audit(Standard::Nist80063b).is_compliant());
+
+    pwq.preset(Preset::Nist80063b).unwrap();
+    let report = pwq.audit(Standard::Nist80063b);
+    assert!(report.is_compliant(), "{report}");
+
+    pwq.preset(Preset::PciDss4).unwrap();
+    let report = pwq.audit(Standard::PciDss4);
+    assert!(report.is_compliant(), "{report}");
+    assert!(report
+        .findings
+        .iter()
+        .any(|f| f.status == Status::NotApplicable));
+
+    pwq.digit_credit(0);
+    assert!(!pwq.audit(Standard::PciDss4).is_compliant());
+}
+
+#[test]
+fn test_lint_config() {
+    let report = lint_config(
+        "# comment\n\
+         minlen = 8\n\
+         nosuchkey = 1\n\
+         minclass = 7\n\
+         dcredit = x\n\
+         minlen = 10\n\
+         badwords = abc secret\n",
+    )
+    .unwrap();
+
+    let lines = report
+        .diagnostics
+        .iter()
+        .filter_map(|d| d.line)
+        .collect::<Vec<_>>();
+    assert_eq!(lines, vec![3, 4, 5, 6]);
+    assert!(report.has_errors());
+    assert!(report
+        .diagnostics
+        .iter()
+        .any(|d| d.line.is_none() && d.key.as_deref() == Some("badwords")));
+    assert_eq!(report.effective_min_length, 10);
+}
+
+#[test]
+fn test_lint() {
+    let pwq = PWQuality::new().unwrap();
+    pwq.min_length(12)
+        .min_class(4)
+        .digit_credit(1)
+        .uppercase_credit(1)
+        .lowercase_credit(1)
+        .other_credit(1);
+
+    let report = pwq.lint();
+    assert_eq!(report.effective_min_length, 8);
+    assert!(report
+        .diagnostics
+        .iter()
+        .any(|d| d.severity == Severity::Warning && d.key.as_deref() == Some("minclass")));
+}
+
+#[test]
+fn test_config_file() {
+    let text = "# Configuration for systemwide password quality limits\n\
+                #\n\
+                # minlen = 8\n\
+                difok=3 # keep\n\
+                \n\
+                # dcredit = 0\n";
+    let mut conf = ConfigFile::parse(text);
+    assert_eq!(conf.to_string(), text);
+    assert_eq!(conf.get("difok"), Some(Some("3")));
+    assert_eq!(conf.get("minlen"), None);
+
+    conf.set("minlen", Some("12")).unwrap();
+    conf.set("difok", Some("5")).unwrap();
+    assert!(conf.set("minlen", Some("twelve")).is_err());
+    let e = conf.set("minclass", Some("5")).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::Integer);
+    assert!(e
+        .text(&Catalog::default())
+        .ends_with(" - minclass: 5 is greater than the maximum 4"));
+    assert!(conf.set("nosuchkey", Some("1")).is_err());
+    assert_eq!(
+        conf.to_string(),
+        "# Configuration for systemwide password quality limits\n\
+         #\n\
+         # minlen = 8\n\
+         minlen = 12\n\
+         difok = 5 # keep\n\
+         \n\
+         # dcredit = 0\n"
+    );
+
+    assert!(conf.unset("minlen"));
+    assert!(!conf.unset("minlen"));
+    assert_eq!(
+        conf.entries().collect::<Vec<_>>(),
+        vec![("difok", Some("5"))]
+    );
+}
+
+#[test]
+fn test_config_file_write() {
+    let path = std::env::temp_dir().join("libpwquality-test-edit.conf");
+    std::fs::write(&path, "# minlen = 8\n").unwrap();
+
+    let mut conf = ConfigFile::read(&path).unwrap();
+    conf.set("minlen", Some("10")).unwrap();
+    conf.write(&path).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "# minlen = 8\nminlen = 10\n"
+    );
+
+    let pwq = PWQuality::new().unwrap();
+    pwq.read_config(&path).unwrap();
+    assert_eq!(pwq.get_min_length(), 10);
+
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(
+        ConfigFile::drop_in_path("50-site").unwrap(),
+        std::path::Path::new(DROP_IN_DIR).join("50-site.conf")
+    );
+    for name in ["../../passwd", "a/b", ".hidden", ""] {
+        assert!(ConfigFile::drop_in_path(name).is_err(), "{name}");
+    }
+}
+
+#[test]
+fn test_migrate_pam() {
+    let system_auth = "\
+# password policy
+password    requisite     pam_cracklib.so try_first_pass retry=3 \\
+                          minlen=12 difignore=23 reject_username
+password    sufficient    pam_unix.so sha512 shadow use_authtok
+";
+    let password_auth = "password [success=1 default=ignore] pam_pwquality.so minlen=10\n";
+
+    let migration = migrate_pam([
+        ("system-auth", system_auth),
+        ("password-auth", password_auth),
+    ])
+    .unwrap();
+
+    let conf = &migration.config;
+    assert_eq!(conf.get("minlen"), Some(Some("12")));
+    // usercheck is only supported since libpwquality 1.4
+    #[cfg(any(feature = "v1_4", feature = "vendored", feature = "vendored-cracklib"))]
+    assert_eq!(conf.get("usercheck"), Some(Some("1")));
+    assert_eq!(conf.get("difok"), Some(Some("5")));
+    assert_eq!(conf.get("dcredit"), Some(Some("1")));
+    assert_eq!(conf.get("difignore"), None);
+
+    let untranslated = migration
+        .untranslated
+        .iter()
+        .map(|u| u.argument.as_str())
+        .collect::<Vec<_>>();
+    assert!(untranslated.contains(&"difignore=23"));
+    assert!(untranslated.contains(&"minlen=10"));
+
+    assert_eq!(migration.changes.len(), 2);
+    assert_eq!(migration.changes[0].location.line, 2);
+    assert!(migration.changes[0].new.contains("pam_pwquality.so"));
+    assert!(!migration.changes[0].new.contains("minlen"));
+    assert!(migration.changes[0]
+        .new
+        .ends_with("pam_pwquality.so try_first_pass retry=3"));
+    assert!(migration.changes[1]
+        .new
+        .starts_with("password [success=1 default=ignore] pam_pwquality.so"));
+}
+
+#[test]
+fn test_option() {
+    let pwq = PWQuality::new().unwrap();
+
+    pwq.set_option("minlen=12").unwrap();
+    assert_eq!(pwq.get_min_length(), 12);
+    assert_eq!(pwq.get_option("minlen").unwrap(), "12");
+
+    pwq.set_option("badwords=foo bar").unwrap();
+    assert_eq!(pwq.get_option("badwords").unwrap(), "foo bar");
+
+    pwq.gecos_check(true);
+    assert_eq!(pwq.get_option("gecoscheck").unwrap(), "1");
+
+    assert!(pwq.set_option("unknown=1").is_err());
+    assert!(pwq.get_option("unknown").is_err());
+
+    for key in Setting::keys() {
+        assert_eq!(key.parse::<Setting>().unwrap().key(), key);
+        assert!(pwq.get_option(key).is_ok());
+    }
+    assert!(Setting::keys().any(|key| key == "dictpath"));
+}
+
+#[test]
+fn test_setting_info() {
+    for setting in Setting::ALL {
+        let info = setting.info();
+        assert!(info.supported);
+        assert_eq!(info.setting(), Some(*setting));
+    }
+
+    let pwq = PWQuality::new().unwrap();
+    for info in SettingInfo::all().iter().filter(|info| info.supported) {
+        if !info.bare && info.value_type != ValueType::List {
+            assert_eq!(
+                pwq.get_option(info.key).unwrap(),
+                info.default,
+                "{}",
+                info.key
+            );
+        }
+    }
+
+    let minlen = SettingInfo::find("minlen").unwrap();
+    assert_eq!(minlen.accessor, "min_length");
+    assert_eq!(minlen.value_type, ValueType::Int);
+    assert!(minlen.validate(Some("12")).is_ok());
+    assert!(minlen.validate(Some("4")).is_err());
+    assert!(minlen.validate(None).is_err());
+
+    assert!(SettingInfo::find("dcredit").unwrap().negative.is_some());
+    assert!(SettingInfo::find("unknown").is_none());
+}
+
+#[test]
+fn test_json_schema() {
+    let schema: serde_json::Value = serde_json::from_str(&json_schema()).unwrap();
+    let properties = schema["properties"].as_object().unwrap();
+
+    assert_eq!(properties.len(), Setting::ALL.len());
+    assert_eq!(properties["minlen"]["type"], "integer");
+    assert_eq!(properties["minlen"]["minimum"], 6);
+    assert_eq!(properties["minlen"]["default"], 8);
+    assert_eq!(properties["gecoscheck"]["type"], "boolean");
+    assert_eq!(properties["badwords"]["type"], "array");
+    assert!(properties["dcredit"]["description"]
+        .as_str()
+        .unwrap()
+        .contains("negative"));
+    assert_eq!(schema["additionalProperties"], false);
+}
+
+#[test]
+fn test_requirements() {
+    let pwq = PWQuality::new().unwrap();
+    pwq.min_length(12)
+        .digit_credit(-2)
+        .uppercase_credit(1)
+        .max_repeat(3)
+        .min_diff(5);
+
+    let requirements = pwq.requirements();
+    assert!(requirements.contains(&Requirement::MinLength {
+        length: 12,
+        effective: 11
+    }));
+    assert!(requirements.contains(&Requirement::MinChars {
+        class: CharClass::Digit,
+        count: 2
+    }));
+    assert!(requirements.contains(&Requirement::Credit {
+        class: CharClass::Uppercase,
+        credit: 1
+    }));
+
+    let text = pwq.describe(&Catalog::default());
+    assert!(text.contains(&"at least 2 digits".to_string()));
+    assert!(text.contains(&"no more than 3 identical characters in a row".to_string()));
+    assert!(
+        text.contains(&"must differ from your old password by at least 5 characters".to_string())
+    );
+
+    pwq.min_diff(1);
+    let requirement = pwq
+        .requirements()
+        .into_iter()
+        .find(|r| matches!(r, Requirement::MinDiff(_)))
+        .unwrap();
+    assert_eq!(requirement, Requirement::MinDiff(1));
+    assert_eq!(
+        requirement.to_string(),
+        "must differ from your old password by at least 1 character"
+    );
+    pwq.min_diff(5);
+
+    let catalog = Catalog::parse(
+        "fr",
+        "# French\n\
+         requirement.min_digits = au moins {n} chiffre|au moins {n} chiffres\n",
+    )
+    .unwrap();
+    assert_eq!(catalog.locale(), "fr");
+    let text = pwq.describe(&catalog);
+    assert!(text.contains(&"au moins 2 chiffres".to_string()));
+    assert!(text.contains(&"no more than 3 identical characters in a row".to_string()));
+
+    assert!(Catalog::parse("fr", "invalid").is_err());
+}
+
+#[test]
+fn test_checklist() {
+    let pwq = PWQuality::new().unwrap();
+    pwq.min_length(10)
+        .digit_credit(-1)
+        .uppercase_credit(1)
+        .max_repeat(2)
+        .min_diff(3);
+
+    let status = |password: &str, requirement: &Requirement| {
+        pwq.checklist(password, Some("oldpassword"), Some("alice"))
+            .into_iter()
+            .find(|item| item.requirement == *requirement)
+            .unwrap()
+            .status
+    };
+    let min_length = Requirement::MinLength {
+        length: 10,
+        effective: 9,
+    };
+    let digit = Requirement::MinChars {
+        class: CharClass::Digit,
+        count: 1,
+    };
+
+    // nothing short-circuits on an empty input
+    let items = pwq.checklist("", None, None);
+    assert_eq!(items.len(), pwq.requirements().len());
+    assert!(items
+        .iter()
+        .any(|item| item.requirement == Requirement::MinDiff(3)
+            && item.status == Status::NotApplicable));
+
+    assert_eq!(status("abcdefgh", &min_length), Status::Unmet);
+    assert_eq!(status("Abcdefghi", &min_length), Status::Met);
+    assert_eq!(status("abcdefgh", &digit), Status::Unmet);
+    assert_eq!(status("abcdefgh1", &digit), Status::Met);
+    assert_eq!(status("abccc", &Requirement::MaxRepeat(2)), Status::Unmet);
+    assert_eq!(status("abcc", &Requirement::MaxRepeat(2)), Status::Met);
+    assert_eq!(status("xalice1", &Requirement::NoUserName), Status::Unmet);
+    assert_eq!(status("xecila1", &Requirement::NoUserName), Status::Unmet);
+    assert_eq!(
+        status("OldPassword", &Requirement::NotOldVariant),
+        Status::Unmet
+    );
+    assert_eq!(
+        status("passwordold", &Requirement::NotOldVariant),
+        Status::Unmet
+    );
+    assert_eq!(
+        status("oldpassw0rd", &Requirement::MinDiff(3)),
+        Status::Unmet
+    );
+    assert_eq!(status("newsecret12", &Requirement::MinDiff(3)), Status::Met);
+    assert_eq!(status("abcba", &Requirement::NotPalindrome), Status::Unmet);
+    assert_eq!(
+        status("abcdefgh1", &Requirement::NoDictionaryWord),
+        Status::NotApplicable
+    );
+}
+
+#[test]
+fn test_suggestions() {
+    let pwq = PWQuality::new().unwrap();
+    pwq.min_length(12).min_class(3).max_repeat(2);
+
+    let suggestions = pwq.suggestions("abcddd", None, Some("alice"));
+    let advice = suggestions
+        .iter()
+        .map(|s| s.advice.clone())
+        .collect::<Vec<_>>();
+    assert!(advice.contains(&Advice::AddCharacters(6)));
+    assert!(advice.contains(&Advice::AvoidRepeat("ddd".to_string())));
+    assert!(suggestions.iter().all(|s| s.required));
+    assert!(suggestions.windows(2).all(|w| w[0].score >= w[1].score));
+
+    let text = suggestions
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    assert!(text.contains(&"add 6 more characters".to_string()));
+    assert!(text.contains(&"avoid repeating characters as in 'ddd'".to_string()));
+    assert!(text
+        .iter()
+        .any(|t| t.starts_with("include characters of 2 more kinds")));
+
+    let suggestions = pwq.suggestions("xalice-Kt9zq!", None, Some("alice"));
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].advice, Advice::AvoidUserName);
+    assert_eq!(
+        suggestions[0].to_string(),
+        "don't reuse parts of your user name"
+    );
+
+    let suggestions = pwq.suggestions("kt9zq-wpmx7r", None, None);
+    assert_eq!(
+        suggestions[0].to_string(),
+        "include at least one uppercase letter"
+    );
+    assert!(!suggestions[0].required);
+
+    pwq.max_class_repeat(2);
+    let suggestions = pwq.suggestions("Kt9zq-w€€€", None, None);
+    assert!(suggestions
+        .iter()
+        .any(|s| s.advice == Advice::AvoidClassRun("€€€".to_string())));
+}
+
+#[test]
+fn test_error_text() {
+    let pwq = PWQuality::new().unwrap();
+    pwq.min_length(14);
+
+    let err = pwq.check("x7Rt!q", None, None).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MinLength);
+    assert_eq!(err.kind().code(), -14);
+    assert_eq!(err.aux_int(), Some(14));
+    assert_eq!(
+        err.text(&Catalog::default()),
+        "The password is shorter than 14 characters"
+    );
+
+    let po = r#"# Polish translation
+msgid ""
+msgstr ""
+"Language: pl\n"
+"Plural-Forms: nplurals=3; plural=(n==1 ? 0 : n%10>=2 && n%10<=4 && "
+"(n%100<10 || n%100>=20) ? 1 : 2);\n"
+
+#: src/error.c:82
+#, c-format
+msgid "The password is shorter than %ld character"
+msgid_plural "The password is shorter than %ld characters"
+msgstr[0] "Hasło jest krótsze niż %ld znak"
+msgstr[1] "Hasło jest krótsze niż %ld znaki"
+msgstr[2] "Hasło jest krótsze niż %ld znaków"
+
+#, fuzzy
+msgid "The password is a palindrome"
+msgstr "Hasło jest palindromem"
+"#;
+    let catalog = Catalog::from_po("pl", po).unwrap();
+    assert_eq!(err.text(&catalog), "Hasło jest krótsze niż 14 znaków");
+    assert_eq!(
+        catalog.format("error.min_length.count", &[("n", "3".to_string())]),
+        "Hasło jest krótsze niż 3 znaki"
+    );
+    assert_eq!(
+        catalog.format("error.palindrome", &[]),
+        "The password is a palindrome"
+    );
+
+    assert!(Catalog::load("/invalid/path", "pl_PL.UTF-8").is_err());
+
+    let catalog = Catalog::bundled("pl_PL.UTF-8").unwrap();
+    assert_eq!(err.text(&catalog), "Hasło jest krótsze niż 14 znaków");
+    assert_eq!(
+        catalog.format("error.palindrome", &[]),
+        "Hasło jest palindromem"
+    );
+    assert_eq!(
+        catalog.format("cracklib.dictionary_word", &[]),
+        "opiera się na słowie ze słownika"
+    );
+    assert!(Catalog::bundled("xx_XX.UTF-8").is_err());
+}
+
+#[test]
+fn test_error_threads() {
+    let handles = (8..16)
+        .map(|len| {
+            std::thread::spawn(move || {
+                let pwq = PWQuality::new().unwrap();
+                pwq.min_length(len);
+
+                (0..100)
+                    .map(|_| pwq.check("x7Rt!", None, None).unwrap_err())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for (len, handle) in (8..16).zip(handles) {
+        let expected = format!("The password is shorter than {len} characters");
+        for err in handle.join().unwrap() {
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+}
+
+#[test]
+fn test_error_code() {
+    let pwq = PWQuality::new().unwrap();
+    pwq.min_length(14);
+
+    let err = pwq.check("x7Rt!q", None, None).unwrap_err();
+    assert_eq!(err.stable_code(), "too_short");
+    assert_eq!(err.param(), Some(("min", "14".to_string())));
+    assert_eq!("too_short".parse::<ErrorKind>(), Ok(ErrorKind::MinLength));
+
+    let err = pwq.check("p@s5w0rD!xyzq", Some("p@s5w0rD!xyzq"), None);
+    assert_eq!(err.unwrap_err().stable_code(), "same_as_old");
+
+    #[cfg(feature = "serde")]
+    {
+        let err = pwq.check("x7Rt!q", None, None).unwrap_err();
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({
+                "code": "too_short",
+                "message": "The password is shorter than 14 characters",
+                "params": { "min": 14 },
+            })
+        );
+
+        let checklist = pwq.checklist("x7Rt!q", None, None);
+        let json = serde_json::to_value(&checklist).unwrap();
+        assert_eq!(
+            json[0],
+            serde_json::json!({
+                "requirement": { "min_length": { "length": 14, "effective": 14 } },
+                "status": "unmet",
+            })
+        );
+    }
+}
+
+#[test]
+fn test_user_context() {
+    let pwq = PWQuality::new().unwrap();
+    pwq.gecos_check(true);
+
+    let context = UserContext {
+        display_name: Some("Ada Lovelace".to_string()),
+        email: Some("countess@analytical.example".to_string()),
+        phone: Some("+44 20 7946 0958".to_string()),
+        organization: Some("Babbage Engines".to_string()),
+        extra: vec!["London".to_string()],
+        ..UserContext::new("ada")
+    };
+
+    let check = |password| {
+        pwq.check_with(
+            password,
+            &CheckContext {
+                user_context: Some(&context),
+                ..Default::default()
+            },
+        )
+    };
+    assert!(check("x7Rt!q9#Lm2&").is_ok());
+
+    for password in [
+        "x7Rt!LOVELACE2&",
+        "x7Rt!ecalevol2&",
+        "x7Rt!babbage2&",
+        "x7Rt!analytical2&",
+        "x7Rt!0958q9#Lm",
+        "x7Rt!London2&",
+    ] {
+        let err = check(password).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::GecosCheck, "{password}");
+    }
+
+    let err = check("x7Rt!countess2&").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UserCheck);
+
+    pwq.gecos_check(false);
+    assert!(check("x7Rt!LOVELACE2&").is_ok());
+
+    #[cfg(any(feature = "v1_4_5", feature = "vendored"))]
+    {
+        // like libpwquality, substrings of 3 characters or fewer are not checked
+        pwq.user_substr(3);
+        assert!(check("x7Rt!cou2&Lm").is_ok());
+        assert!(check("x7Rt!ssetn2&Lm").is_ok());
+
+        pwq.user_substr(4);
+        for password in ["x7Rt!coun2&Lm", "x7Rt!ssetn2&Lm"] {
+            let err = check(password).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::UserCheck, "{password}");
+        }
+    }
+}
+
+#[test]
+fn test_passwd_entries() {
+    let pwq = PWQuality::new().unwrap();
+    pwq.gecos_check(true)
+        .passwd_entries([PasswdEntry::new("ada", "Ada Lovelace,Room 42,,")]);
+
+    assert!(pwq.is_local_user("ada"));
+    assert!(!pwq.is_local_user("root"));
+
+    let err = pwq.check("x7Rt!lovelace2&", None, Some("ada")).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::GecosCheck);
+    assert!(pwq.get_gecos_check());
+    assert!(pwq.check("x7Rt!lovelace2&", None, Some("bob")).is_ok());
+
+    let path = std::env::temp_dir().join(format!("pwquality-passwd-{}", std::process::id()));
+    std::fs::write(
+        &path,
+        "# image users\nroot:x:0:0:root:/root:/bin/sh\n+@nis\nbob:x:1000:1000:Bob Marley:/home/bob:/bin/sh\n",
+    )
+    .unwrap();
+    pwq.passwd_file(&path);
+
+    assert!(pwq.is_local_user(b"bob"));
+    assert!(!pwq.is_local_user("ada"));
+    let err = pwq.check("x7Rt!YELRAM2&", None, Some("bob")).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::GecosCheck);
+
+    std::fs::remove_file(&path).unwrap();
+
+    let entry = "bob:x:1000:1000:Bob Marley:/home/bob:/bin/sh"
+        .parse::<PasswdEntry>()
+        .unwrap();
+    assert_eq!(entry.uid, 1000);
+    assert_eq!(
+        entry.to_string(),
+        "bob:x:1000:1000:Bob Marley:/home/bob:/bin/sh"
+    );
+    assert!("bob:x:1000".parse::<PasswdEntry>().is_err());
+}
+
+#[test]
+fn test_check_with_words() {
+    let pwq = PWQuality::new().unwrap();
+    pwq.bad_words(["globex"]).unwrap();
+
+    let context = CheckContext {
+        words: &["initech", "tps"],
+        ..Default::default()
+    };
+    assert!(pwq.check_with("x7Rt!q9#Lm2&", &context).is_ok());
+    assert!(pwq.check("x7Rt!INITECH2&", None, None).is_ok());
+
+    let err = pwq.check_with("x7Rt!INITECH2&", &context).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BadWords);
+    assert_eq!(err.param(), Some(("word", "initech".to_string())));
+    assert_eq!(
+        err.to_string(),
+        "The password contains forbidden words in some form"
+    );
+
+    // words of 3 characters or less are ignored, like by libpwquality
+    assert!(pwq.check_with("x7Rt!tps#Lm2&", &context).is_ok());
+
+    let err = pwq.check_with("x7Rt!Globex2&", &context).unwrap_err();
+    assert_eq!(err.aux_str(), Some("globex"));
+}
+
+#[test]
+fn test_check_history() {
+    let pwq = PWQuality::new().unwrap();
+    pwq.min_diff(5);
+
+    let history = ["Zq8#mW2!pLx7", "Hj4$tN9@vRc1", "Kd7&yB3^sQf6"];
+    let context = CheckContext {
+        old_password: Some(history[0]),
+        history: &history[1..],
+        ..Default::default()
+    };
+    assert!(pwq.check_with("x7Rt!q9#Lm2&", &context).is_ok());
+
+    for (password, kind) in [
+        ("Kd7&yB3^sQf6", ErrorKind::SamePassword),
+        ("hJ4$Tn9@VrC1", ErrorKind::CaseChangesOnly),
+        ("Kd7&yB3^sQf9", ErrorKind::TooSimilar),
+        ("3^sQf6Kd7&yB", ErrorKind::Rotated),
+    ] {
+        let err = pwq.check_with(password, &context).unwrap_err();
+        assert_eq!(err.kind(), kind, "{password}");
+    }
+
+    // like libpwquality, reversals are accepted, and so are passwords at least twice as
+    // long as the old one
+    let short = ["Kd7&yB3^sQf6", "q9#L"];
+    let context = CheckContext {
+        history: &short,
+        ..Default::default()
+    };
+    assert!(pwq.check_with("6fQs^3By&7dK", &context).is_ok());
+    assert!(pwq.check_with("q9#Lx7Rt", &context).is_ok());
+
+    #[cfg(feature = "history")]
+    {
+        let path = std::env::temp_dir().join(format!("pwquality-history-{}", std::process::id()));
+        let mut store = libpwquality::PasswordHistory::open(&path, 2).unwrap();
+        assert!(store.is_empty());
+
+        for password in history {
+            store.push(password).unwrap();
+        }
+        assert_eq!(store.len(), 2);
+
+        let store = libpwquality::PasswordHistory::open(&path, 2).unwrap();
+        assert!(store.contains("Kd7&yB3^sQf6"));
+        assert!(!store.contains("Zq8#mW2!pLx7"));
+        assert!(!std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("Kd7&yB3^sQf6"));
+
+        let context = CheckContext {
+            store: Some(&store),
+            ..Default::default()
+        };
+        let err = pwq.check_with("Hj4$tN9@vRc1", &context).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SamePassword);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
+
+#[test]
+fn test_similarity() {
+    let old = "Kd7&yB3^sQf6";
+    let metrics = [Similarity::Damerau(3), Similarity::CommonSubstring(0.5)];
+
+    assert_eq!(
+        PWQuality::similarity(old, old, &metrics),
+        Some(SimilarityReason::Same)
+    );
+    assert_eq!(
+        PWQuality::similarity("kD7&Yb3^SqF6", old, &metrics),
+        Some(SimilarityReason::CaseSwapped)
+    );
+    assert_eq!(
+        PWQuality::similarity("6fQs^3By&7dK", old, &metrics),
+        Some(SimilarityReason::Reversed)
+    );
+    assert_eq!(
+        PWQuality::similarity("^sQf6Kd7&yB3", old, &metrics),
+        Some(SimilarityReason::Rotated)
+    );
+
+    // a transposition is a single edit for Damerau, two for Levenshtein
+    let swapped = "dK7&yB3^sQf6";
+    assert_eq!(
+        PWQuality::similarity(swapped, old, &metrics),
+        Some(SimilarityReason::Edits(1))
+    );
+    assert_eq!(
+        PWQuality::similarity(swapped, old, &[Similarity::Levenshtein(2)]),
+        None
+    );
+
+    let reason = PWQuality::similarity("Kd7&yB3^!!x!m!", old, &metrics[1..]).unwrap();
+    assert_eq!(
+        reason.to_string(),
+        "the password shares 'Kd7&yB3^' with the old one, 57% of its length"
+    );
+
+    assert!(PWQuality::similarity("x7Rt!q9#Lm2&", old, &metrics).is_none());
+
+    let pwq = PWQuality::new().unwrap();
+    let context = CheckContext {
+        old_password: Some(old),
+        metrics: &metrics,
+        ..Default::default()
+    };
+    let err = pwq.check_with("Kd7&yB3^sQf6!", &context).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TooSimilar);
+    assert_eq!(err.similarity_reason(), Some(&SimilarityReason::Edits(1)));
+    assert_eq!(
+        err.param(),
+        Some((
+            "reason",
+            "the password is only 1 edit away from the old one".to_string()
+        ))
+    );
+    assert_eq!(
+        err.text(&Catalog::default()),
+        "The password is too similar to the old one - \
+         the password is only 1 edit away from the old one"
+    );
+    assert!(pwq.check_with("x7Rt!q9#Lm2&", &context).is_ok());
+}
+
+#[test]
+fn test_rules() {
+    struct Seasons;
+
+    impl Rule for Seasons {
+        fn check(
+            &self,
+            password: &str,
+            _old_password: Option<&str>,
+            _user: Option<&str>,
+        ) -> Result<i32, PWQError> {
+            let lower = password.to_lowercase();
+
+            if ["spring", "summer", "autumn", "winter"]
+                .iter()
+                .any(|season| lower.contains(season))
+            {
+                Err(PWQError::rule("season", "The password contains a season").unwrap())
+            } else {
+                Ok(-10)
+            }
+        }
+    }
+
+    let pwq = PWQuality::new().unwrap();
+    let password = "x7Rt!q9#Lm2&";
+    let score = pwq.check(password, None, None).unwrap();
+
+    pwq.add_rule(Seasons)
+        .add_rule(|password: &str, _: Option<&str>, _: Option<&str>| {
+            if password.contains("TCK-") {
+                Err(
+                    PWQError::rule("ticket_number", "The password contains a ticket number")
+                        .unwrap()
+                        .with_message_id("custom.ticket"),
+                )
+            } else {
+                Ok(0)
+            }
+        });
+
+    assert_eq!(pwq.check(password, None, None), Ok((score - 10).max(0)));
+
+    let err = pwq.check("Summer#2025x!Qz", None, None).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Rule("season".into()));
+    assert_eq!(err.stable_code(), "season");
+    assert_eq!(err.to_string(), "The password contains a season");
+    assert_eq!(err.param(), None);
+
+    let err = pwq.check("q9#Lm2&TCK-4821", None, None).unwrap_err();
+    assert_eq!(err.stable_code(), "ticket_number");
+    assert_eq!(
+        err.text(&Catalog::default()),
+        "The password contains a ticket number"
+    );
+    let mut catalog = Catalog::new("pl");
+    catalog.insert("custom.ticket", "Hasło zawiera numer zgłoszenia");
+    assert_eq!(err.text(&catalog), "Hasło zawiera numer zgłoszenia");
+
+    // the built-in checks run first
+    let err = pwq.check("Summer", None, None).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MinLength);
+
+    pwq.clear_rules();
+    assert_eq!(pwq.check(password, None, None), Ok(score));
+
+    let tenant = 7;
+    let err = PWQError::rule(format!("tenant_{tenant}"), "The password is banned").unwrap();
+    assert_eq!(err.stable_code(), "tenant_7");
+    assert_eq!(err.kind().code(), -1);
+    assert!(PWQError::rule("too_short", "Too short").is_err());
+    assert!(PWQError::rule("unknown", "Unknown").is_err());
+
+    // a rule can add rules, which run from the next check on
+    let pwq = std::rc::Rc::new(PWQuality::new().unwrap());
+    let inner = std::rc::Rc::downgrade(&pwq);
+    pwq.add_rule(move |_: &str, _: Option<&str>, _: Option<&str>| {
+        if let Some(pwq) = inner.upgrade() {
+            pwq.add_rule(|_: &str, _: Option<&str>, _: Option<&str>| Ok(-1));
+        }
+        Ok(0)
+    });
+    let score = pwq.check(password, None, None).unwrap();
+    assert_eq!(pwq.check(password, None, None), Ok(score - 1));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_deny_patterns() {
+    use libpwquality::DenyPattern;
+
+    let path = std::env::temp_dir().join(format!("pwquality-patterns-{}", std::process::id()));
+    std::fs::write(
+        &path,
+        "minlen 10\n\
+         denypattern = ^Summer20\\d\\d # seasons\n\
+         denypattern = {policy.company} (?i)acme\\d+\n",
+    )
+    .unwrap();
+
+    let pwq = PWQuality::new().unwrap();
+    pwq.read_config(&path).unwrap();
+
+    assert_eq!(pwq.get_min_length(), 10);
+    let patterns = pwq.get_deny_patterns();
+    assert_eq!(patterns.len(), 2);
+    assert_eq!(patterns[0].as_str(), "^Summer20\\d\\d");
+    assert_eq!(patterns[1].get_message_id(), Some("policy.company"));
+    assert_eq!(patterns[1].to_string(), "{policy.company} (?i)acme\\d+");
+
+    let err = pwq.check("Summer2025!xQz", None, None).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Rule("denied_pattern".into()));
+    assert_eq!(err.to_string(), "The password matches a forbidden pattern");
+
+    let err = pwq.check("k9#mACME42vQ!", None, None).unwrap_err();
+    let mut catalog = Catalog::new("en");
+    catalog.insert("policy.company", "The password contains the company name");
+    assert_eq!(err.text(&catalog), "The password contains the company name");
+
+    assert!(pwq.check("x7Rt!q9#Lm2&", None, None).is_ok());
+
+    pwq.clear_deny_patterns()
+        .set_option("denypattern=qwerty")
+        .unwrap()
+        .deny_pattern(
+            DenyPattern::new("(?i)winter")
+                .unwrap()
+                .message_id("policy.season"),
+        );
+    assert_eq!(pwq.get_deny_patterns().len(), 2);
+    assert!(pwq.check("Summer2025!xQz", None, None).is_ok());
+    assert!(pwq.check("x7Rt!qwerty&9", None, None).is_err());
+
+    let err = pwq.set_option("denypattern=(unclosed").err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::CfgfileMalformed);
+    let (name, reason) = err.param().unwrap();
+    assert_eq!(name, "reason");
+    assert!(reason.contains("\"(unclosed\"") && reason.contains("unclosed group"));
+    assert!(err.to_string().ends_with(&reason));
+    assert!("{id".parse::<DenyPattern>().is_err());
+    assert!("{bad id} x".parse::<DenyPattern>().is_err());
+
+    // without deny patterns, the error of libpwquality is kept
+    std::fs::write(&path, "minlen = 10\nnosuchsetting = 1\n").unwrap();
+    let err = PWQuality::new().unwrap().read_config(&path).err().unwrap();
+    assert_ne!(err.kind(), ErrorKind::CfgfileOpen);
+    std::fs::remove_file(&path).unwrap();
+    let err = PWQuality::new().unwrap().read_config(&path).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::CfgfileOpen);
+
+    let mut conf = ConfigFile::default();
+    let err = conf.set("denypattern", Some("(unclosed")).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::CfgfileMalformed);
+    assert!(err.param().unwrap().1.contains("unclosed group"));
+
+    let report = lint_config("denypattern = a(b\ndenypattern = ^abc\n").unwrap();
+    assert_eq!(report.diagnostics.len(), 1);
+    assert_eq!(report.diagnostics[0].line, Some(1));
+}
@@ -0,0 +1,101 @@
+use libpwquality::{PWQError, PWQuality};
+
+/// Arguments of the `pam_pwquality` module, as given in a PAM stack file.
+///
+/// Arguments that are not handled by the module itself are kept as pwquality
+/// `name=value` settings, which override the configuration file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleArgs {
+    /// Log debugging information to syslog.
+    pub debug: bool,
+    /// Don't prompt for the new password but use the one of a previous module.
+    pub use_authtok: bool,
+    /// Word used in the prompt instead of "UNIX", e.g. `New LDAP password:`, given by
+    /// `type=` and set as the `PAM_AUTHTOK_TYPE` item.
+    ///
+    /// `authtok_type=` is read from the module arguments by `pam_get_authtok(3)` itself.
+    pub authtok_type: Option<String>,
+    /// Number of times the user is prompted before returning an error.
+    pub retry: Option<i32>,
+    /// Enforce the checks for root.
+    pub enforce_for_root: bool,
+    /// Only check users from the local passwd file.
+    pub local_users_only: bool,
+    /// Remaining pwquality settings in `name=value` form.
+    pub settings: Vec<String>,
+}
+
+impl ModuleArgs {
+    /// Parse module arguments.
+    pub fn parse<I>(args: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut parsed = Self::default();
+
+        for arg in args {
+            let arg = arg.as_ref();
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (arg, None),
+            };
+
+            match (name, value) {
+                ("debug", None) => parsed.debug = true,
+                ("use_authtok", None) => parsed.use_authtok = true,
+                ("enforce_for_root", None) => parsed.enforce_for_root = true,
+                ("local_users_only", None) => parsed.local_users_only = true,
+                ("type", Some(value)) => parsed.authtok_type = Some(value.to_string()),
+                ("authtok_type", Some(_)) => {}
+                ("retry", Some(value)) if value.parse::<i32>().is_ok() => {
+                    parsed.retry = value.parse().ok()
+                }
+                _ => parsed.settings.push(arg.to_string()),
+            }
+        }
+
+        parsed
+    }
+
+    /// Apply the pwquality settings to the given instance.
+    ///
+    /// Returns the settings that were rejected, which the module ignores.
+    pub fn apply(&self, pwq: &PWQuality) -> Vec<(String, PWQError)> {
+        self.settings
+            .iter()
+            .filter_map(|s| pwq.set_option(s).err().map(|e| (s.clone(), e)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_args() {
+        let args = ModuleArgs::parse([
+            "retry=3",
+            "minlen=12",
+            "enforce_for_root",
+            "type=LDAP",
+            "authtok_type=NIS",
+            "dcredit=x",
+            "debug",
+        ]);
+
+        assert_eq!(args.retry, Some(3));
+        assert!(args.enforce_for_root);
+        assert!(args.debug);
+        assert!(!args.local_users_only);
+        assert_eq!(args.authtok_type.as_deref(), Some("LDAP"));
+        assert_eq!(args.settings, vec!["minlen=12", "dcredit=x"]);
+
+        let pwq = PWQuality::new().unwrap();
+        let rejected = args.apply(&pwq);
+        assert_eq!(pwq.get_min_length(), 12);
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].0, "dcredit=x");
+    }
+}
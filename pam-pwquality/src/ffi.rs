@@ -0,0 +1,67 @@
+//! Minimal bindings to Linux-PAM used by the module.
+
+#![allow(non_camel_case_types)]
+
+use std::os::raw::{c_char, c_int, c_void};
+
+pub const PAM_SUCCESS: c_int = 0;
+pub const PAM_SERVICE_ERR: c_int = 3;
+pub const PAM_SYSTEM_ERR: c_int = 4;
+pub const PAM_MAXTRIES: c_int = 11;
+pub const PAM_AUTHTOK_ERR: c_int = 20;
+pub const PAM_INCOMPLETE: c_int = 31;
+pub const PAM_CONV_AGAIN: c_int = 30;
+
+pub const PAM_ERROR_MSG: c_int = 3;
+
+pub const PAM_AUTHTOK: c_int = 6;
+pub const PAM_OLDAUTHTOK: c_int = 7;
+pub const PAM_AUTHTOK_TYPE: c_int = 13;
+
+pub const PAM_CHANGE_EXPIRED_AUTHTOK: c_int = 0x0020;
+pub const PAM_UPDATE_AUTHTOK: c_int = 0x2000;
+pub const PAM_PRELIM_CHECK: c_int = 0x4000;
+
+#[repr(C)]
+pub struct pam_handle_t {
+    _private: [u8; 0],
+}
+
+#[link(name = "pam")]
+extern "C" {
+    pub fn pam_get_item(
+        pamh: *const pam_handle_t,
+        item_type: c_int,
+        item: *mut *const c_void,
+    ) -> c_int;
+
+    pub fn pam_set_item(pamh: *mut pam_handle_t, item_type: c_int, item: *const c_void) -> c_int;
+
+    pub fn pam_get_user(
+        pamh: *mut pam_handle_t,
+        user: *mut *const c_char,
+        prompt: *const c_char,
+    ) -> c_int;
+
+    pub fn pam_get_authtok_noverify(
+        pamh: *mut pam_handle_t,
+        authtok: *mut *const c_char,
+        prompt: *const c_char,
+    ) -> c_int;
+
+    pub fn pam_get_authtok_verify(
+        pamh: *mut pam_handle_t,
+        authtok: *mut *const c_char,
+        prompt: *const c_char,
+    ) -> c_int;
+
+    pub fn pam_prompt(
+        pamh: *mut pam_handle_t,
+        style: c_int,
+        response: *mut *mut c_char,
+        fmt: *const c_char,
+        ...
+    ) -> c_int;
+
+    pub fn pam_syslog(pamh: *const pam_handle_t, priority: c_int, fmt: *const c_char, ...);
+}
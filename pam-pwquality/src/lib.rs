@@ -0,0 +1,239 @@
+//! PAM module for password quality checking built on libpwquality.
+//!
+//! The module is a memory-safe replacement of `pam_pwquality.so` and implements the
+//! password management group only. It understands the same module arguments:
+//!
+//! * `debug`: Log debugging information to syslog.
+//! * `retry=N`: Prompt the user at most N times before returning an error.
+//! * `enforce_for_root`: Enforce the checks for root as well.
+//! * `local_users_only`: Skip the checks for users not in the local passwd file.
+//! * `type=XXX`: Set the `PAM_AUTHTOK_TYPE` item, the word used in the prompts.
+//! * `authtok_type=XXX`, `use_authtok`: Handled by `pam_get_authtok(3)`.
+//! * Any other `name=value` pwquality setting, overriding `pwquality.conf`.
+//!
+//! ```text
+//! password requisite libpam_pwquality.so retry=3 minlen=12
+//! password sufficient pam_unix.so use_authtok
+//! ```
+
+mod args;
+mod ffi;
+
+use args::ModuleArgs;
+use ffi::*;
+use libpwquality::PWQuality;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::ptr::{null, null_mut};
+
+struct Options {
+    args: ModuleArgs,
+    pwq: PWQuality,
+    retry: i32,
+    enforcing: bool,
+    enforce_for_root: bool,
+    local_users_only: bool,
+}
+
+fn syslog(pamh: *const pam_handle_t, priority: c_int, msg: &str) {
+    let msg = CString::new(msg.replace('\0', "")).unwrap_or_default();
+
+    unsafe { pam_syslog(pamh, priority, c"%s".as_ptr(), msg.as_ptr()) }
+}
+
+fn error(pamh: *mut pam_handle_t, msg: &str) {
+    let msg = CString::new(msg.replace('\0', "")).unwrap_or_default();
+
+    unsafe {
+        pam_prompt(
+            pamh,
+            PAM_ERROR_MSG,
+            null_mut(),
+            c"%s".as_ptr(),
+            msg.as_ptr(),
+        );
+    }
+}
+
+fn options(pamh: *mut pam_handle_t, args: ModuleArgs) -> Option<Options> {
+    let pwq = PWQuality::new().ok()?;
+
+    if let Some(authtok_type) = &args.authtok_type {
+        let authtok_type = CString::new(authtok_type.as_str()).ok()?;
+        unsafe { pam_set_item(pamh, PAM_AUTHTOK_TYPE, authtok_type.as_ptr().cast()) };
+    }
+
+    if let Err(e) = pwq.read_default_config() {
+        syslog(
+            pamh,
+            libc::LOG_ERR,
+            &format!("Reading pwquality configuration file failed: {e}"),
+        );
+    }
+
+    for (setting, e) in args.apply(&pwq) {
+        syslog(
+            pamh,
+            libc::LOG_ERR,
+            &format!("pam_parse: unknown or broken option; ignored - {setting}: {e}"),
+        );
+    }
+
+    #[cfg(any(feature = "v1_4_1", feature = "vendored"))]
+    let (retry, enforce_for_root, local_users_only) = (
+        args.retry.unwrap_or(pwq.get_retry_times()),
+        args.enforce_for_root || pwq.get_enforce_for_root(),
+        args.local_users_only || pwq.get_local_users_only(),
+    );
+
+    #[cfg(not(any(feature = "v1_4_1", feature = "vendored")))]
+    let (retry, enforce_for_root, local_users_only) = (
+        args.retry.unwrap_or(1),
+        args.enforce_for_root,
+        args.local_users_only,
+    );
+
+    #[cfg(any(feature = "v1_4", feature = "vendored"))]
+    let enforcing = pwq.get_enforcing();
+
+    #[cfg(not(any(feature = "v1_4", feature = "vendored")))]
+    let enforcing = true;
+
+    Some(Options {
+        args,
+        pwq,
+        retry: retry.max(1),
+        enforcing,
+        enforce_for_root,
+        local_users_only,
+    })
+}
+
+unsafe fn get_item(pamh: *mut pam_handle_t, item_type: c_int) -> Option<&'static CStr> {
+    let mut item = null();
+
+    if pam_get_item(pamh, item_type, &mut item) == PAM_SUCCESS {
+        item.cast::<c_char>().as_ref().map(|p| CStr::from_ptr(p))
+    } else {
+        None
+    }
+}
+
+unsafe fn chauthtok(pamh: *mut pam_handle_t, flags: c_int, args: ModuleArgs) -> c_int {
+    if flags & PAM_PRELIM_CHECK != 0 {
+        return PAM_SUCCESS;
+    }
+
+    if flags & PAM_UPDATE_AUTHTOK == 0 {
+        return PAM_SERVICE_ERR;
+    }
+
+    let Some(options) = options(pamh, args) else {
+        return PAM_SYSTEM_ERR;
+    };
+
+    let mut user = null();
+    let ret = pam_get_user(pamh, &mut user, null());
+    let Some(user) = user.as_ref().filter(|_| ret == PAM_SUCCESS) else {
+        syslog(pamh, libc::LOG_ERR, "Can not get username");
+        return PAM_AUTHTOK_ERR;
+    };
+    let user = CStr::from_ptr(user).to_bytes();
+
+    let old_password = get_item(pamh, PAM_OLDAUTHTOK).map(CStr::to_bytes);
+    let skip = options.local_users_only && !options.pwq.is_local_user(user);
+    let enforced = options.enforcing
+        && (libc::getuid() != 0
+            || options.enforce_for_root
+            || flags & PAM_CHANGE_EXPIRED_AUTHTOK != 0);
+
+    let mut ret = PAM_AUTHTOK_ERR;
+    for _ in 0..options.retry {
+        let mut token = null();
+        ret = pam_get_authtok_noverify(pamh, &mut token, null());
+        if ret != PAM_SUCCESS {
+            syslog(
+                pamh,
+                libc::LOG_ERR,
+                "pam_get_authtok_noverify returned error",
+            );
+            return if ret == PAM_CONV_AGAIN {
+                PAM_INCOMPLETE
+            } else {
+                ret
+            };
+        }
+
+        let Some(password) = token.as_ref().map(|p| CStr::from_ptr(p).to_bytes()) else {
+            return PAM_AUTHTOK_ERR;
+        };
+
+        if !skip {
+            match options.pwq.check_bytes(password, old_password, Some(user)) {
+                Ok(score) => {
+                    if options.args.debug {
+                        syslog(pamh, libc::LOG_DEBUG, &format!("password score: {score}"));
+                    }
+                }
+                Err(e) => {
+                    if options.args.debug {
+                        syslog(pamh, libc::LOG_DEBUG, &format!("bad password: {e}"));
+                    }
+
+                    error(pamh, &format!("BAD PASSWORD: {e}"));
+
+                    if enforced {
+                        pam_set_item(pamh, PAM_AUTHTOK, null());
+                        ret = PAM_AUTHTOK_ERR;
+                        continue;
+                    }
+                }
+            }
+        }
+
+        // the new password is compared with the one passed in
+        ret = pam_get_authtok_verify(pamh, &mut token, null());
+        if ret != PAM_SUCCESS {
+            syslog(pamh, libc::LOG_ERR, "pam_get_authtok_verify returned error");
+            pam_set_item(pamh, PAM_AUTHTOK, null());
+            continue;
+        }
+
+        // the user aborted the password change
+        if token.is_null() {
+            return PAM_AUTHTOK_ERR;
+        }
+
+        return PAM_SUCCESS;
+    }
+
+    pam_set_item(pamh, PAM_AUTHTOK, null());
+
+    // with a single try, the real reason can be returned
+    if options.retry > 1 {
+        PAM_MAXTRIES
+    } else {
+        ret
+    }
+}
+
+/// Password management entry point of the module.
+///
+/// # Safety
+///
+/// Must only be called by libpam with a valid handle and module arguments.
+#[no_mangle]
+pub unsafe extern "C" fn pam_sm_chauthtok(
+    pamh: *mut pam_handle_t,
+    flags: c_int,
+    argc: c_int,
+    argv: *const *const c_char,
+) -> c_int {
+    let args = (0..argc.max(0) as usize)
+        .filter_map(|i| argv.add(i).read().as_ref())
+        .map(|p| CStr::from_ptr(p).to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+
+    std::panic::catch_unwind(|| chauthtok(pamh, flags, ModuleArgs::parse(args)))
+        .unwrap_or(PAM_SYSTEM_ERR)
+}
@@ -0,0 +1,31 @@
+#!/bin/sh
+# Test the module with pam_wrapper and pamtester without touching /etc/pam.d.
+#
+# Requires pam_wrapper (libpam_wrapper.so) and pamtester to be installed.
+set -eu
+
+cd "$(dirname "$0")/.."
+cargo build
+module="$(cargo metadata --format-version 1 --no-deps |
+	sed -n 's/.*"target_directory":"\([^"]*\)".*/\1/p')/debug/libpam_pwquality.so"
+
+service_dir="$(mktemp -d)"
+trap 'rm -rf "$service_dir"' EXIT
+sed "s|@MODULE@|$module|" tests/pwquality-test >"$service_dir/pwquality-test"
+
+run() {
+	printf '%s\n' "$@" |
+		LD_PRELOAD=libpam_wrapper.so PAM_WRAPPER=1 PAM_WRAPPER_SERVICE_DIR="$service_dir" \
+			pamtester pwquality-test "$(id -un)" chauthtok
+}
+
+# a strong password typed twice is accepted
+run 'Xk7#vq9!Lm2$' 'Xk7#vq9!Lm2$'
+
+# a short password is rejected on both tries
+if run short short; then
+	echo "short password accepted" >&2
+	exit 1
+fi
+
+echo "ok"